@@ -0,0 +1,206 @@
+use super::{*, scc::DFSearch, NodeState::{Discovered, Processed, Undiscovered}};
+
+// ANCHOR: graphs_bcc_state
+/// Low-link state of an undirected graph's depth first search, where every node gets an entry time along with a low-link value,
+/// that is, the earliest entry time reachable from the node's subtree through a single back edge
+/// A child whose subtree cannot reach above its parent is cut off once the parent is removed
+struct BState {
+    tracker: Tracker<usize>,
+    low: HashMap<Node, usize>,
+    time: usize,
+    /// number of children per node in the depth first search tree
+    children: HashMap<Node, usize>,
+    /// nodes whose edge back to their parent has been skipped, so that a parallel edge counts as a back edge
+    skipped: HashSet<Node>,
+    /// edges explored but not yet assigned to a biconnected component
+    stack: Vec<(Node, Node)>,
+    articulation: HashSet<Node>,
+    bridges: Vec<(Node, Node)>,
+    components: Vec<Vec<Node>>
+}
+
+impl BState {
+    /// Construct a new `BState` given a `Graph`
+    fn new<W>(g: &Graph<W>) -> BState {
+        BState {
+            tracker: g.get_tracker(Undiscovered, 0, None),
+            low: HashMap::new(),
+            time: 0,
+            children: HashMap::new(),
+            skipped: HashSet::new(),
+            stack: Vec::new(),
+            articulation: HashSet::new(),
+            bridges: Vec::new(),
+            components: Vec::new()
+        }
+    }
+}
+
+impl<W: Weight> DFSearch<W> for BState {
+    type Output = Vec<Vec<Node>>;
+
+    /// capture the entry time, which is also the initial low-link
+    fn pre_process_node(&mut self, node: Node) -> &mut Self {
+        self.time += 1;
+        self.tracker[node].visited(Discovered).distance(self.time);
+        self.low.insert(node, self.time);
+        self
+    }
+
+    /// pass the low-link on to the parent and check whether the node's subtree is cut off once the parent is removed
+    fn post_process_node(&mut self, node: Node) -> &mut Self {
+        self.tracker[node].visited(Processed);
+        let low = self.low[&node];
+        let Some(parent) = self.tracker[node].parent else {
+            // the root is an articulation point only when it has more than one subtree
+            if self.children.get(&node).is_some_and(|&c| c > 1) {
+                self.articulation.insert(node);
+            }
+            return self
+        };
+        self.low.entry(parent).and_modify(|l| *l = low.min(*l));
+
+        let entry = self.tracker[parent].dist;
+        if low >= entry {
+            // the subtree cannot reach above the parent, hence its edges form a biconnected component
+            if self.tracker[parent].parent.is_some() {
+                self.articulation.insert(parent);
+            }
+            let pos = self.stack.iter().rposition(|&e| e == (parent, node)).expect("tree edge is on the stack");
+            let mut component = self.stack.split_off(pos).into_iter()
+                .flat_map(|(src, dst)| [src, dst])
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect::<Vec<_>>();
+            component.sort_unstable();
+            self.components.push(component);
+        }
+        if low > entry {
+            // not even the parent can be reached, hence the edge is the only one connecting the subtree
+            self.bridges.push((parent.min(node), parent.max(node)));
+        }
+        self
+    }
+
+    /// an undiscovered `dst` becomes the child of `src`, while an earlier discovered `dst` is a back edge
+    /// that lowers the low-link of `src`; the edge back to the parent is skipped once, as it's the tree edge itself
+    fn pre_process_edge(&mut self, edge: Edge<W>) -> &mut Self {
+        let Edge(src, dst) = edge;
+        let dst = Node::from(dst);
+        if self.tracker[src].parent == Some(dst) && self.skipped.insert(src) {
+            return self
+        }
+        match self.tracker[dst].visited {
+            Undiscovered => {
+                self.tracker[dst].parent(src);
+                *self.children.entry(src).or_default() += 1;
+                self.stack.push((src, dst));
+            },
+            // an ancestor still being explored; an edge to a descendant has already been seen from the other end
+            _ if self.tracker[dst].dist < self.tracker[src].dist => {
+                let entry = self.tracker[dst].dist;
+                self.low.entry(src).and_modify(|l| *l = entry.min(*l));
+                self.stack.push((src, dst));
+            },
+            _ => {}
+        }
+        self
+    }
+
+    /// return the biconnected components found so far
+    fn path(&self) -> &Self::Output {
+        &self.components
+    }
+
+    /// return whether the node has been visited
+    fn is_discovered(&self, node: Node) -> bool {
+        self.tracker[node].is_discovered()
+    }
+}
+// ANCHOR_END: graphs_bcc_state
+// ANCHOR: graphs_bcc
+/// Single points of failure of an undirected graph, found with a single depth first search
+pub trait Biconnectivity {
+    /// Nodes whose removal increases the number of connected components, in ascending order
+    fn articulation_points(&self) -> Result<Vec<Node>, GraphError>;
+    /// Edges whose removal increases the number of connected components, as `(src, dst)` pairs with `src < dst` in ascending order
+    fn bridges(&self) -> Result<Vec<(Node, Node)>, GraphError>;
+    /// Maximal sets of nodes that remain connected after removing any single node, each one in ascending order
+    /// Components share their articulation points, while isolated nodes are not part of any component
+    fn biconnected_components(&self) -> Result<Vec<Vec<Node>>, GraphError>;
+}
+
+impl<W: Weight> Graph<W> {
+    /// Runs the low-link depth first search from every node not yet visited
+    fn low_link(&self) -> Result<BState, GraphError> {
+        // articulation points & bridges are defined over undirected graphs
+        self.ensure_kind(GraphKind::Undirected)?;
+
+        let mut bs = BState::new(self);
+        for &start in &self.nodes {
+            if !bs.tracker[start].is_discovered() {
+                bs.path_search(self, start);
+            }
+        }
+        Ok(bs)
+    }
+}
+
+impl<W: Weight> Biconnectivity for Graph<W> {
+    fn articulation_points(&self) -> Result<Vec<Node>, GraphError> {
+        let mut points = self.low_link()?.articulation.into_iter().collect::<Vec<_>>();
+        points.sort_unstable();
+        Ok(points)
+    }
+    fn bridges(&self) -> Result<Vec<(Node, Node)>, GraphError> {
+        let mut bridges = self.low_link()?.bridges;
+        bridges.sort_unstable();
+        Ok(bridges)
+    }
+    fn biconnected_components(&self) -> Result<Vec<Vec<Node>>, GraphError> {
+        Ok(self.low_link()?.components)
+    }
+}
+// ANCHOR_END: graphs_bcc
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::generate;
+
+    #[test]
+    fn test_biconnectivity() {
+        // two triangles 1-2-3 and 4-5-6 joined by the bridge 3-4, plus the dangling edge 6-7
+        let g = Graph::from_edge_list(&[
+            (1, 2, 1), (2, 3, 1), (3, 1, 1), (3, 4, 1), (4, 5, 1), (5, 6, 1), (6, 4, 1), (6, 7, 1)
+        ], GraphKind::Undirected);
+        assert_eq!(g.articulation_points().expect("graph is undirected"), vec![3, 4, 6]);
+        assert_eq!(g.bridges().expect("graph is undirected"), vec![(3, 4), (6, 7)]);
+        let mut bcc = g.biconnected_components().expect("graph is undirected");
+        bcc.sort();
+        assert_eq!(bcc, vec![vec![1, 2, 3], vec![3, 4], vec![4, 5, 6], vec![6, 7]]);
+
+        // a parallel edge is never a bridge
+        let mut g = Graph::from_edge_list(&[(1, 2, 1), (2, 3, 1)], GraphKind::Undirected);
+        g.insert_edge(2, NC(3, 5));
+        assert_eq!(g.bridges().expect("graph is undirected"), vec![(1, 2)]);
+        assert_eq!(g.articulation_points().expect("graph is undirected"), vec![2]);
+
+        // a grid has no single point of failure
+        let g: Graph = generate::grid(4, 4, GraphKind::Undirected);
+        assert!(g.articulation_points().expect("graph is undirected").is_empty());
+        assert!(g.bridges().expect("graph is undirected").is_empty());
+        assert_eq!(g.biconnected_components().expect("graph is undirected"), vec![(1..=16).collect::<Vec<_>>()]);
+
+        let g = Graph::from_edge_list(&[(1, 2, 1)], GraphKind::Directed);
+        assert!(matches!(g.bridges(), Err(GraphError::KindMismatch { .. })));
+    }
+    #[test]
+    fn test_biconnectivity_tree() {
+        // every edge of a tree is a bridge and every inner node an articulation point
+        let g: Graph = generate::grid(1, 50, GraphKind::Undirected);
+        assert_eq!(g.bridges().expect("graph is undirected"), (1..50).map(|n| (n, n + 1)).collect::<Vec<_>>());
+        assert_eq!(g.articulation_points().expect("graph is undirected"), (2..50).collect::<Vec<_>>());
+        assert_eq!(g.biconnected_components().expect("graph is undirected").len(), 49);
+    }
+}
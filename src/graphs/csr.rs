@@ -0,0 +1,414 @@
+use std::collections::{BinaryHeap, VecDeque};
+use super::{*, path_search::PathSearch, scc::ConnectedComponents};
+
+/// Compressed Sparse Row (CSR) representation of a `Graph`, that is, a frozen read-only graph
+/// where node ids are compacted to dense indices `0..n` and all edges are laid out in a single `Vec`
+/// - the edges of the node at index `i` are found at `targets[offsets[i]..offsets[i+1]]`
+/// - the cost of each edge is found at the same position in `costs`
+///
+/// Node state is kept in `Vec`s indexed by the dense index, hence no hashing takes place while traversing
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrGraph<W = Cost> {
+    /// original `Node` id per dense index, sorted in ascending order
+    ids: Vec<Node>,
+    /// position of each node's first edge, with `offsets[n]` marking the end of the last node's edges
+    offsets: Vec<usize>,
+    /// dense index of each edge's destination node
+    targets: Vec<usize>,
+    /// cost of each edge; unweighted edges cost `W::ONE`
+    costs: Vec<W>,
+    kind: GraphKind
+}
+
+impl<W: Weight> From<&Graph<W>> for CsrGraph<W> {
+    /// Freezes a `Graph` into its CSR representation
+    fn from(g: &Graph<W>) -> Self {
+        let mut ids = g.nodes.iter().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        let mut offsets = Vec::with_capacity(ids.len() + 1);
+        let mut targets = Vec::new();
+        let mut costs = Vec::new();
+
+        offsets.push(0);
+        for node in &ids {
+            if let Some(edges) = g.edges.get(node) {
+                for &dst in edges {
+                    let (dst, cost) = match dst {
+                        NodeType::N(dst) => (dst, W::ONE),
+                        NC(dst, cost) => (dst, cost)
+                    };
+                    targets.push(ids.binary_search(&dst).expect("CsrGraph::from(): edge destination isn't a graph node"));
+                    costs.push(cost);
+                }
+            }
+            offsets.push(targets.len());
+        }
+        CsrGraph { ids, offsets, targets, costs, kind: g.kind }
+    }
+}
+
+impl<W: Weight> CsrGraph<W> {
+    /// Number of nodes in the graph
+    pub fn node_count(&self) -> usize {
+        self.ids.len()
+    }
+    /// Number of stored edges; an undirected graph stores both directions of an edge
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+    pub fn is_directed(&self) -> bool {
+        self.kind == GraphKind::Directed
+    }
+    /// Returns an error unless the graph is of the given `kind`
+    pub fn ensure_kind(&self, kind: GraphKind) -> Result<(), GraphError> {
+        if self.kind == kind {
+            Ok(())
+        } else {
+            Err(GraphError::KindMismatch { expected: kind, found: self.kind })
+        }
+    }
+    /// Returns the graph nodes in ascending order
+    pub fn nodes(&self) -> impl Iterator<Item=Node> + '_ {
+        self.ids.iter().copied()
+    }
+    /// Returns the `(dst, cost)` edges of `node`, if `node` is part of the graph
+    pub fn edges(&self, node: Node) -> Option<impl Iterator<Item=(Node, W)> + '_> {
+        let idx = self.index(node)?;
+        Some(self.neighbours(idx).map(|(dst, cost)| (self.ids[dst], cost)))
+    }
+    /// Returns the dense index of `node`
+    fn index(&self, node: Node) -> Option<usize> {
+        self.ids.binary_search(&node).ok()
+    }
+    /// Returns the `(dst, cost)` edges of the node at dense index `idx`
+    fn neighbours(&self, idx: usize) -> impl Iterator<Item=(usize, W)> + '_ {
+        let range = self.offsets[idx]..self.offsets[idx + 1];
+        self.targets[range.clone()].iter().copied()
+            .zip(self.costs[range].iter().copied())
+    }
+    /// Reverses the edges of a directed graph; an undirected graph is its own transpose
+    pub fn transpose(&self) -> CsrGraph<W> {
+        if self.kind == GraphKind::Undirected {
+            return self.clone()
+        }
+        // count the incoming edges per node so to lay out the reversed edges
+        let n = self.node_count();
+        let mut offsets = vec![0; n + 1];
+        self.targets.iter().for_each(|&dst| offsets[dst + 1] += 1);
+        (0..n).for_each(|i| offsets[i + 1] += offsets[i]);
+
+        // fill in each reversed edge at the next free position of its new source
+        let mut next = offsets.clone();
+        let mut targets = vec![0; self.edge_count()];
+        let mut costs = vec![W::ZERO; self.edge_count()];
+        for src in 0..n {
+            for (dst, cost) in self.neighbours(src) {
+                targets[next[dst]] = src;
+                costs[next[dst]] = cost;
+                next[dst] += 1;
+            }
+        }
+        CsrGraph { ids: self.ids.clone(), offsets, targets, costs, kind: self.kind }
+    }
+    /// Backtraces the `parent` links from `goal` and returns the path in original `Node` ids
+    fn extract_path(&self, parent: &[Option<usize>], goal: usize) -> Vec<Node> {
+        let mut path = VecDeque::new();
+        let mut cur = Some(goal);
+        while let Some(idx) = cur {
+            path.push_front(self.ids[idx]);
+            cur = parent[idx];
+        }
+        path.into()
+    }
+}
+
+impl<W: Weight> PathSearch<W> for CsrGraph<W> {
+    /// Breadth first search for the path with the least number of edges
+    fn path_distance(&self, start: Node, goal: Node) -> Option<(Vec<Node>, W)> {
+        let (start, goal) = (self.index(start)?, self.index(goal)?);
+
+        let mut dist = vec![W::ZERO; self.node_count()];
+        let mut parent = vec![None; self.node_count()];
+        let mut discovered = vec![false; self.node_count()];
+        let mut queue = VecDeque::from([start]);
+        discovered[start] = true;
+
+        while let Some(src) = queue.pop_front() {
+            if src == goal {
+                return Some((self.extract_path(&parent, goal), dist[goal]))
+            }
+            for (dst, _) in self.neighbours(src) {
+                if !discovered[dst] {
+                    discovered[dst] = true;
+                    dist[dst] = dist[src] + W::ONE;
+                    parent[dst] = Some(src);
+                    queue.push_back(dst);
+                }
+            }
+        }
+        None
+    }
+    /// Dijkstra's search for the path with the minimum cost
+    fn path_shortest(&self, start: Node, goal: Node) -> Option<(Vec<Node>, W)> {
+        self.path_astar(start, goal, |_| W::ZERO)
+    }
+    /// A* search for the path with the minimum cost; a zero heuristic results to Dijkstra's search
+    fn path_astar<H: Fn(Node) -> W>(&self, start: Node, goal: Node, heuristic: H) -> Option<(Vec<Node>, W)> {
+        let (start, goal) = (self.index(start)?, self.index(goal)?);
+
+        let mut dist = vec![W::MAX; self.node_count()];
+        let mut parent = vec![None; self.node_count()];
+        // `NodeType` orders in reverse cost, hence the heap pops the lowest (estimated) cost node first
+        let mut queue = BinaryHeap::from([NC(start, heuristic(self.ids[start]))]);
+        dist[start] = W::ZERO;
+
+        while let Some(NC(src, estimate)) = queue.pop() {
+            // skip stale entries, that is, those queued before a cheaper path to the node was found;
+            // no node is ever closed, so that an inconsistent heuristic still results to the cheapest path
+            if estimate > dist[src] + heuristic(self.ids[src]) { continue }
+            if src == goal {
+                return Some((self.extract_path(&parent, goal), dist[goal]))
+            }
+            for (dst, edge_cost) in self.neighbours(src) {
+                let edge_cost = dist[src] + edge_cost;
+                if edge_cost < dist[dst] {
+                    dist[dst] = edge_cost;
+                    parent[dst] = Some(src);
+                    queue.push(NC(dst, edge_cost + heuristic(self.ids[dst])));
+                }
+            }
+        }
+        None
+    }
+    /// Bidirectional Dijkstra's search, running forward on the graph and backward on its transpose
+    fn path_bidirectional(&self, start: Node, goal: Node) -> Option<(Vec<Node>, W)> {
+        let (start, goal) = (self.index(start)?, self.index(goal)?);
+        if start == goal {
+            return Some((vec![self.ids[start]], W::ZERO))
+        }
+        let n = self.node_count();
+        let graphs = [self, &self.transpose()];
+        let mut dist = [vec![W::MAX; n], vec![W::MAX; n]];
+        let mut parent = [vec![None; n], vec![None; n]];
+        let mut processed = [vec![false; n], vec![false; n]];
+        let mut queue = [BinaryHeap::from([NC(start, W::ZERO)]), BinaryHeap::from([NC(goal, W::ZERO)])];
+        dist[0][start] = W::ZERO;
+        dist[1][goal] = W::ZERO;
+        let mut best: Option<(W, usize)> = None;
+
+        let top = |queue: &BinaryHeap<NodeType<W>>| queue.peek().map(|&nt| match nt { NC(_, cost) => cost, NodeType::N(_) => unreachable!() });
+        // expand the side with the lowest cost in its queue, until the two lowest costs
+        // add up to more than the best path found so far
+        while let (Some(f), Some(b)) = (top(&queue[0]), top(&queue[1])) {
+            if best.is_some_and(|(cost, _)| f + b >= cost) { break }
+            let (side, other) = if f <= b { (0, 1) } else { (1, 0) };
+
+            let Some(NC(src, cost)) = queue[side].pop() else { break };
+            if processed[side][src] { continue }
+            processed[side][src] = true;
+            for (dst, edge_cost) in graphs[side].neighbours(src) {
+                let edge_cost = cost + edge_cost;
+                if !processed[side][dst] && edge_cost < dist[side][dst] {
+                    dist[side][dst] = edge_cost;
+                    parent[side][dst] = Some(src);
+                    queue[side].push(NC(dst, edge_cost));
+                }
+                // a path through `src -> dst` joins the two searches
+                if dist[other][dst] != W::MAX && best.is_none_or(|(c, _)| edge_cost + dist[other][dst] < c) {
+                    best = Some((edge_cost + dist[other][dst], dst));
+                }
+            }
+        }
+
+        // join the forward path start -> meet with the reversed backward path goal -> meet
+        let (cost, meet) = best?;
+        let mut path = self.extract_path(&parent[0], meet);
+        let mut back = self.extract_path(&parent[1], meet);
+        back.pop();
+        path.extend(back.into_iter().rev());
+        Some((path, cost))
+    }
+}
+
+impl<W: Weight> ConnectedComponents for CsrGraph<W> {
+    /// Kosaraju's algorithm using an explicit stack in place of recursion,
+    /// so that long paths in large graphs cannot overflow the call stack
+    fn strongly_connected(&self) -> Result<Vec<Vec<Node>>, GraphError> {
+        self.ensure_kind(GraphKind::Directed)?;
+        let n = self.node_count();
+
+        // Pass 1: order nodes by exit time, that is, a node is pushed once all its edges have been explored
+        let mut discovered = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        // (node, position of next edge to explore)
+        let mut stack = Vec::new();
+        for start in 0..n {
+            if discovered[start] { continue }
+            discovered[start] = true;
+            stack.push((start, self.offsets[start]));
+            while let Some((node, pos)) = stack.last_mut() {
+                let node = *node;
+                if *pos == self.offsets[node + 1] {
+                    stack.pop();
+                    order.push(node);
+                    continue
+                }
+                let dst = self.targets[*pos];
+                *pos += 1;
+                if !discovered[dst] {
+                    discovered[dst] = true;
+                    stack.push((dst, self.offsets[dst]));
+                }
+            }
+        }
+
+        // Pass 2: explore the transposed graph by decreasing exit time; each search yields a component
+        let tg = self.transpose();
+        let mut discovered = vec![false; n];
+        let mut components = Vec::new();
+        for &start in order.iter().rev() {
+            if discovered[start] { continue }
+            discovered[start] = true;
+            let mut component = Vec::new();
+            let mut stack = vec![start];
+            while let Some(node) = stack.pop() {
+                component.push(self.ids[node]);
+                for (dst, _) in tg.neighbours(node) {
+                    if !discovered[dst] {
+                        discovered[dst] = true;
+                        stack.push(dst);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        Ok(components)
+    }
+    /// Tarjan's algorithm using an explicit stack in place of recursion
+    fn strongly_connected_tarjan(&self) -> Result<Vec<Vec<Node>>, GraphError> {
+        self.ensure_kind(GraphKind::Directed)?;
+        let n = self.node_count();
+
+        // entry index and low-link per node, with `usize::MAX` for undiscovered nodes
+        let (mut index, mut low) = (vec![usize::MAX; n], vec![usize::MAX; n]);
+        let mut on_stack = vec![false; n];
+        let (mut stack, mut components) = (Vec::new(), Vec::new());
+        let mut time = 0;
+        for start in 0..n {
+            if index[start] != usize::MAX { continue }
+            // (node, position of next edge to explore)
+            let mut dfs = vec![(start, self.offsets[start])];
+            (index[start], low[start], on_stack[start]) = (time, time, true);
+            stack.push(start);
+            time += 1;
+            while let Some((node, pos)) = dfs.last_mut() {
+                let node = *node;
+                if *pos < self.offsets[node + 1] {
+                    let dst = self.targets[*pos];
+                    *pos += 1;
+                    if index[dst] == usize::MAX {
+                        (index[dst], low[dst], on_stack[dst]) = (time, time, true);
+                        stack.push(dst);
+                        time += 1;
+                        dfs.push((dst, self.offsets[dst]));
+                    } else if on_stack[dst] {
+                        low[node] = low[node].min(index[dst]);
+                    }
+                    continue
+                }
+                // all edges explored; pass the low-link on to the parent
+                dfs.pop();
+                if let Some(&(parent, _)) = dfs.last() {
+                    low[parent] = low[parent].min(low[node]);
+                }
+                // the node is the root of a component
+                if low[node] == index[node] {
+                    let pos = stack.iter().rposition(|&n| n == node).expect("node is on the stack");
+                    let component = stack.split_off(pos);
+                    component.iter().for_each(|&n| on_stack[n] = false);
+                    components.push(component.into_iter().map(|n| self.ids[n]).collect());
+                }
+            }
+        }
+        Ok(components)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cmp::Reverse, fs::File, io::BufReader};
+    use super::*;
+
+    fn load(filename: &str, node_pat: char, edge_pat: char) -> Graph {
+        Graph::import_text_graph(BufReader::new(File::open(filename).expect("cannot open file")), node_pat, edge_pat, GraphKind::Directed)
+            .expect("graph couldn't be loaded")
+    }
+
+    #[test]
+    fn test_csr_layout() {
+        let g = Graph::from_edge_list(&[(10, 20, 1), (10, 30, 2), (30, 20, 3)], GraphKind::Directed);
+        let csr = CsrGraph::from(&g);
+        assert_eq!(csr.node_count(), 3);
+        assert_eq!(csr.edge_count(), 3);
+        assert_eq!(csr.nodes().collect::<Vec<_>>(), vec![10, 20, 30]);
+        let mut edges = csr.edges(10).expect("node exists").collect::<Vec<_>>();
+        edges.sort();
+        assert_eq!(edges, vec![(20, 1), (30, 2)]);
+        assert_eq!(csr.edges(20).expect("node exists").count(), 0);
+        assert!(csr.edges(40).is_none());
+        assert_eq!(csr.transpose().edges(20).expect("node exists").collect::<HashSet<_>>(), HashSet::from([(10, 1), (30, 3)]));
+    }
+    #[test]
+    fn test_csr_path_search() {
+        let g = load("src/graphs/txt/ps_input_random_10_16.txt", '\t', ',');
+        let csr = CsrGraph::from(&g);
+
+        [7, 37, 59, 82, 99, 115, 133, 165, 188, 197].into_iter()
+            .for_each(|goal| {
+                assert_eq!(csr.path_shortest(1, goal).map(|(_, c)| c), g.path_shortest(1, goal).map(|(_, c)| c));
+                assert_eq!(csr.path_distance(1, goal).map(|(_, c)| c), g.path_distance(1, goal).map(|(_, c)| c));
+            });
+        let (path, cost) = csr.path_shortest(1, 7).expect("path exists");
+        assert_eq!(path.first(), Some(&1));
+        assert_eq!(path.last(), Some(&7));
+        assert_eq!(cost, 588);
+        assert_eq!(csr.path_shortest(1, 1000), None);
+        [(1, 7), (59, 82), (200, 1), (5, 5)].into_iter()
+            .for_each(|(start, goal)| {
+                let path = csr.path_bidirectional(start, goal);
+                assert_eq!(path.as_ref().map(|(_, c)| *c), g.path_shortest(start, goal).map(|(_, c)| c));
+                assert_eq!(path.and_then(|(p, _)| p.last().copied()), Some(goal));
+            });
+
+        // same as `Graph`, an admissible yet inconsistent heuristic must still result to the cheapest path
+        let g = Graph::from_edge_list(&[(1, 2, 1), (2, 4, 1), (1, 3, 1), (3, 4, 2), (4, 5, 3)], GraphKind::Directed);
+        let inconsistent = |node: Node| if node == 2 { 4 } else { 0 };
+        let expected = Some((vec![1, 2, 4, 5], 5));
+        assert_eq!(g.path_astar(1, 5, inconsistent), expected);
+        assert_eq!(CsrGraph::from(&g).path_astar(1, 5, inconsistent), expected);
+    }
+    #[test]
+    fn test_csr_scc() {
+        let test_data = vec![
+            ("src/graphs/txt/scc_simple.txt", vec![3,2,1,1,0]),
+            ("src/graphs/txt/scc_input_mostlyCycles_30_800.txt", vec![437,256,51,44,10]),
+            ("src/graphs/txt/scc_input_mostlyCycles_50_20000.txt", vec![12634,6703,253,139,113])
+        ];
+        for (fname, sizes) in test_data {
+            let csr = CsrGraph::from(&load(fname, ' ', '\0'));
+            for mut scc in [csr.strongly_connected(), csr.strongly_connected_tarjan()].map(|scc| scc.expect("graph is directed")) {
+                scc.sort_by_key(|a| Reverse(a.len()));
+                let found = scc.iter()
+                    .map(|v| v.len())
+                    .chain(std::iter::repeat(0))
+                    .take(5)
+                    .collect::<Vec<_>>();
+                assert_eq!(found, sizes, "{fname}");
+            }
+        }
+        let g = Graph::from_edge_list(&[(1, 2, 1)], GraphKind::Undirected);
+        assert!(matches!(CsrGraph::from(&g).strongly_connected(), Err(GraphError::KindMismatch { .. })));
+        assert!(matches!(CsrGraph::from(&g).strongly_connected_tarjan(), Err(GraphError::KindMismatch { .. })));
+    }
+}
@@ -0,0 +1,249 @@
+use std::fmt::Write;
+use super::{*, ParseErrorKind::*};
+
+/// Attributes applied to highlighted nodes and edges
+const HIGHLIGHT: &str = "color=red, penwidth=2";
+
+impl<W: Weight> Graph<W> {
+    /// Renders the graph in Graphviz DOT format
+    /// - a directed graph renders as `digraph` with `->` edges
+    /// - an undirected graph renders as `graph` with `--` edges, each edge rendered once
+    /// - weighted edges carry their cost as the edge `label`
+    pub fn to_dot(&self) -> String {
+        self.to_dot_highlighted(&HashSet::new(), &HashSet::new())
+    }
+    /// Renders the graph in Graphviz DOT format, highlighting the given subset of nodes and edges,
+    /// e.g. `mst.nodes` & `mst.export_edges()` of the tree returned by `mst_prim()`
+    pub fn to_dot_highlighted(&self, nodes: &HashSet<Node>, edges: &HashSet<Edge<W>>) -> String {
+        let (header, edge_op) = match self.kind {
+            GraphKind::Directed => ("digraph", "->"),
+            GraphKind::Undirected => ("graph", "--")
+        };
+        let mut out = format!("{header} {{\n");
+
+        // sort nodes & edges so the output is stable across runs
+        let mut node_list = self.nodes.iter().copied().collect::<Vec<_>>();
+        node_list.sort_unstable();
+        for node in node_list {
+            match nodes.contains(&node) {
+                true => writeln!(out, "    {node} [{HIGHLIGHT}];"),
+                false => writeln!(out, "    {node};")
+            }.expect("writing to a String cannot fail");
+        }
+
+        let mut edge_list = self.edges.iter()
+            .flat_map(|(&src, dst_nodes)| dst_nodes.iter().map(move |&dst| Edge(src, dst)))
+            // an undirected edge is stored in both directions, hence we only render src -> dst, with src <= dst
+            .filter(|&Edge(src, dst)| self.kind == GraphKind::Directed || src <= dst.into())
+            .collect::<Vec<_>>();
+        edge_list.sort_unstable_by_key(|&Edge(src, dst)| (src, Node::from(dst)));
+        for edge in edge_list {
+            let Edge(src, dst) = edge;
+            let mut attrs = Vec::new();
+            if let NC(_, cost) = dst {
+                attrs.push(format!("label=\"{cost}\""));
+            }
+            if edges.contains(&edge)
+                || (self.kind == GraphKind::Undirected && edges.contains(&Edge(dst.into(), dst.reverse(src)))) {
+                attrs.push(HIGHLIGHT.to_string());
+            }
+            match attrs.is_empty() {
+                true => writeln!(out, "    {src} {edge_op} {};", Node::from(dst)),
+                false => writeln!(out, "    {src} {edge_op} {} [{}];", Node::from(dst), attrs.join(", "))
+            }.expect("writing to a String cannot fail");
+        }
+        out.push_str("}\n");
+        out
+    }
+    /// Loads a graph from Graphviz DOT format; the graph kind follows the `digraph` / `graph` header
+    /// Supported statements are nodes, edges incl. chains such as `1 -> 2 -> 3`, and attribute lists;
+    /// an edge `label` is read as the edge cost, otherwise the edge is unweighted, while all other
+    /// attributes, `graph`/`node`/`edge` defaults and `//` comments are ignored. Subgraphs are not supported
+    pub fn from_dot<R: BufRead>(reader: R) -> Result<Graph<W>, GraphError> {
+        let mut p = DotParser::new(reader)?;
+
+        let mut t = p.next()?;
+        if t.txt == "strict" { t = p.next()? }
+        let (mut g, edge_op) = match t.txt.as_str() {
+            "digraph" => (Graph::new(GraphKind::Directed), "->"),
+            "graph" => (Graph::new(GraphKind::Undirected), "--"),
+            _ => return Err(t.unexpected())
+        };
+        // skip the optional graph name
+        let mut t = p.next()?;
+        if t.txt != "{" { t = p.next()? }
+        if t.txt != "{" { return Err(t.unexpected()) }
+
+        loop {
+            let t = p.next()?;
+            match t.txt.as_str() {
+                "}" => break,
+                ";" => continue,
+                // default attribute statements
+                "graph" | "node" | "edge" => { p.attributes()?; },
+                // graph attribute, e.g. `rankdir=LR`
+                _ if p.peek() == Some("=") => { p.next()?; p.next()?; },
+                _ => {
+                    // node statement or a chain of edges
+                    let mut chain = vec![t.node()?];
+                    while let Some("->" | "--") = p.peek() {
+                        let op = p.next()?;
+                        if op.txt != edge_op { return Err(op.unexpected()) }
+                        chain.push(p.next()?.node()?);
+                    }
+                    let cost = match p.attributes()?.into_iter().find(|(key, _)| key == "label") {
+                        Some((_, t)) => Some(
+                            W::from_str(&t.txt).map_err(|_| GraphError::parse(t.line, t.column, InvalidCost(t.txt.clone())))?
+                        ),
+                        None => None
+                    };
+                    chain.iter().for_each(|&node| { g.nodes.insert(node); });
+                    chain.windows(2)
+                        .for_each(|pair| g.insert_edge(pair[0], cost.map_or(NodeType::N(pair[1]), |cost| NC(pair[1], cost))));
+                }
+            }
+        }
+        Ok(g)
+    }
+}
+
+/// A DOT token along with its position, as it appears in a text editor
+struct Token {
+    txt: String,
+    line: usize,
+    column: usize
+}
+
+impl Token {
+    fn unexpected(&self) -> GraphError {
+        GraphError::parse(self.line, self.column, UnexpectedToken(self.txt.clone()))
+    }
+    fn node(&self) -> Result<Node, GraphError> {
+        Node::from_str(&self.txt).map_err(|_| GraphError::parse(self.line, self.column, InvalidNode(self.txt.clone())))
+    }
+}
+
+/// Splits DOT input into tokens and hands them out to the parser
+struct DotParser {
+    tokens: VecDeque<Token>,
+    /// position right after the last token, reported when input ends unexpectedly
+    end: (usize, usize)
+}
+
+impl DotParser {
+    fn new<R: BufRead>(reader: R) -> Result<DotParser, GraphError> {
+        // a `-` starts an edge operator only when followed by `>` or `-`, otherwise it's part of an ID, e.g. `-3`
+        let is_op = |chars: &[char], i: usize| chars[i] == '-' && matches!(chars.get(i + 1), Some('>' | '-'));
+        let is_delim = |chars: &[char], i: usize| chars[i].is_whitespace() || "{}[];,=\"".contains(chars[i]) || is_op(chars, i);
+
+        let mut tokens = VecDeque::new();
+        let mut end = (1, 1);
+        for (num, line) in reader.lines().enumerate() {
+            let num = num + 1;
+            let line = line.map_err(|error| GraphError::Io { line: num, error })?;
+            let chars = line.chars().collect::<Vec<_>>();
+
+            let mut i = 0;
+            while i < chars.len() {
+                let column = i + 1;
+                let txt = match chars[i] {
+                    c if c.is_whitespace() => { i += 1; continue },
+                    '/' if chars.get(i + 1) == Some(&'/') => break,
+                    '{' | '}' | '[' | ']' | ';' | ',' | '=' => { i += 1; chars[i - 1].to_string() },
+                    _ if is_op(&chars, i) => { i += 2; chars[i - 2..i].iter().collect() },
+                    '"' => {
+                        // quoted ID; the quotes are dropped
+                        let len = chars[i + 1..].iter().position(|&c| c == '"')
+                            .ok_or(GraphError::parse(num, column, MissingToken))?;
+                        i += len + 2;
+                        chars[column..column + len].iter().collect()
+                    },
+                    _ => {
+                        let start = i;
+                        while i < chars.len() && !is_delim(&chars, i) { i += 1 }
+                        chars[start..i].iter().collect()
+                    }
+                };
+                tokens.push_back(Token { txt, line: num, column });
+            }
+            end = (num, chars.len() + 1);
+        }
+        Ok(DotParser { tokens, end })
+    }
+    fn peek(&self) -> Option<&str> {
+        self.tokens.front().map(|t| t.txt.as_str())
+    }
+    fn next(&mut self) -> Result<Token, GraphError> {
+        self.tokens.pop_front().ok_or(GraphError::parse(self.end.0, self.end.1, MissingToken))
+    }
+    /// Consumes an optional `[key=value, ...]` attribute list and returns its `(key, value)` pairs
+    fn attributes(&mut self) -> Result<Vec<(String, Token)>, GraphError> {
+        let mut attrs = Vec::new();
+        if self.peek() != Some("[") { return Ok(attrs) }
+        self.next()?;
+        loop {
+            let key = self.next()?;
+            match key.txt.as_str() {
+                "]" => break,
+                "," | ";" => continue,
+                _ => {
+                    let eq = self.next()?;
+                    if eq.txt != "=" { return Err(eq.unexpected()) }
+                    attrs.push((key.txt, self.next()?));
+                }
+            }
+        }
+        Ok(attrs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::weight::OrderedFloat;
+
+    #[test]
+    fn test_to_dot() {
+        let g = Graph::from_edge_list(&[(1, 2, 3), (2, 3, 4)], GraphKind::Directed);
+        assert_eq!(g.to_dot(), "digraph {\n    1;\n    2;\n    3;\n    1 -> 2 [label=\"3\"];\n    2 -> 3 [label=\"4\"];\n}\n");
+
+        let g = Graph::from_edge_list(&[(1, 2, 3), (2, 3, 4)], GraphKind::Undirected);
+        let out = g.to_dot_highlighted(&HashSet::from([3]), &HashSet::from([Edge(3, NC(2, 4))]));
+        assert_eq!(out, "graph {\n    1;\n    2;\n    3 [color=red, penwidth=2];\n    1 -- 2 [label=\"3\"];\n    2 -- 3 [label=\"4\", color=red, penwidth=2];\n}\n");
+    }
+    #[test]
+    fn test_dot_round_trip() {
+        let g = Graph::from_edge_list(&[(1, 2, 3), (2, 3, -4), (3, 1, 5)], GraphKind::Directed);
+        assert_eq!(Graph::from_dot(g.to_dot().as_bytes()).expect("valid dot"), g);
+
+        let g = Graph::from_edge_list(&[(1, 2, OrderedFloat(0.5)), (2, 3, OrderedFloat(1.25))], GraphKind::Undirected);
+        assert_eq!(Graph::from_dot(g.to_dot().as_bytes()).expect("valid dot"), g);
+
+        let mut g = Graph::<Cost>::import_edges(&[vec![1, 2, 3], vec![2, 3]], GraphKind::Directed).expect("valid edges");
+        g.nodes.insert(4);
+        assert_eq!(Graph::from_dot(g.to_dot().as_bytes()).expect("valid dot"), g);
+    }
+    #[test]
+    fn test_from_dot() {
+        let input = "strict digraph G {\n  rankdir=LR; node [shape=box]\n  // comment\n  1 -> 2 -> 3 [label=7, color=\"blue\"]\n  4\n}\n";
+        let g = Graph::<Cost>::from_dot(input.as_bytes()).expect("valid dot");
+        assert!(g.is_directed());
+        assert_eq!(g.nodes, HashSet::from([1, 2, 3, 4]));
+        assert_eq!(g.edges[&1], HashSet::from([NC(2, 7)]));
+        assert_eq!(g.edges[&2], HashSet::from([NC(3, 7)]));
+
+        let test_data = [
+            ("digraph {\n  1 -- 2\n}", (2, 5, UnexpectedToken("--".to_string()))),
+            ("graph {\n  1 -- x\n}", (2, 8, InvalidNode("x".to_string()))),
+            ("graph {\n  1 -- 2 [label=a]\n}", (2, 17, InvalidCost("a".to_string()))),
+            ("tree {}", (1, 1, UnexpectedToken("tree".to_string()))),
+            ("graph {\n  1 -- 2", (2, 9, MissingToken)),
+        ];
+        for (input, (line, column, kind)) in test_data {
+            match Graph::<Cost>::from_dot(input.as_bytes()) {
+                Err(GraphError::Parse { line: l, column: c, kind: k }) => assert_eq!((l, c, k), (line, column, kind)),
+                other => panic!("Expected parse error, got {:?}", other)
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Random graph generators for tests and benchmarks
+//! Nodes are numbered `1..=n`, as with the `txt` fixtures, and edges are unweighted unless stated otherwise;
+//! use `with_random_costs()` to assign costs to any generated graph.
+//! Every random generator takes an explicit `seed`, hence the same seed always produces the same graph
+use std::ops::Range;
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom, distributions::uniform::SampleUniform};
+use super::*;
+
+/// Erdős–Rényi `G(n,p)` graph, that is, every possible edge between two distinct nodes exists with probability `p`
+/// A directed graph considers `src -> dst` and `dst -> src` as two separate edges
+pub fn erdos_renyi<W: Weight>(n: usize, p: f64, kind: GraphKind, seed: u64) -> Graph<W> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut g = empty(n, kind);
+    for src in 1..=n {
+        // an undirected edge is only drawn once, for src < dst
+        let dst_nodes = match kind {
+            GraphKind::Directed => 1..=n,
+            GraphKind::Undirected => src+1..=n
+        };
+        for dst in dst_nodes {
+            if src != dst && rng.gen_bool(p) {
+                g.insert_edge(src, NodeType::N(dst));
+            }
+        }
+    }
+    g
+}
+
+/// Random undirected `d`-regular graph, that is, every node has exactly `d` edges with no self-loops or parallel edges
+/// Stubs are paired at random and the pairing restarts whenever it gets stuck, which is fast for small `d`
+/// Returns `None` when no such graph exists, that is, when `d >= n` or `n * d` is odd
+pub fn random_regular<W: Weight>(n: usize, d: usize, seed: u64) -> Option<Graph<W>> {
+    if d >= n.max(1) || !(n * d).is_multiple_of(2) {
+        return None
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    'restart: loop {
+        let mut g = empty(n, GraphKind::Undirected);
+        // each node holds `d` stubs waiting to be paired up
+        let mut stubs = (1..=n).flat_map(|node| std::iter::repeat_n(node, d)).collect::<Vec<_>>();
+        while !stubs.is_empty() {
+            // give up on this pairing after a number of unsuitable picks
+            let mut attempts = 0;
+            let (i, j) = loop {
+                let (i, j) = (rng.gen_range(0..stubs.len()), rng.gen_range(0..stubs.len()));
+                let (src, dst) = (stubs[i], stubs[j]);
+                if src != dst && !g.neighbors(src).any(|node| node == dst) {
+                    break (i, j)
+                }
+                attempts += 1;
+                if attempts > stubs.len() * stubs.len() { continue 'restart }
+            };
+            g.insert_edge(stubs[i], NodeType::N(stubs[j]));
+            // remove the higher index first so the lower one stays valid
+            stubs.swap_remove(i.max(j));
+            stubs.swap_remove(i.min(j));
+        }
+        return Some(g)
+    }
+}
+
+/// `rows x cols` grid graph with every node connected to its right and lower neighbours
+/// Node `(row, col)` is numbered `row * cols + col + 1`; a directed grid is therefore acyclic
+/// The grid is fully determined by its dimensions, hence no seed is needed
+pub fn grid<W: Weight>(rows: usize, cols: usize, kind: GraphKind) -> Graph<W> {
+    let mut g = empty(rows * cols, kind);
+    for row in 0..rows {
+        for col in 0..cols {
+            let node = row * cols + col + 1;
+            if col + 1 < cols { g.insert_edge(node, NodeType::N(node + 1)) }
+            if row + 1 < rows { g.insert_edge(node, NodeType::N(node + cols)) }
+        }
+    }
+    g
+}
+
+/// Complete undirected graph with every edge's cost drawn uniformly from `costs`,
+/// similar to the `cst_input_completeRandom_*` fixtures
+pub fn complete_weighted<W: Weight + SampleUniform>(n: usize, costs: Range<W>, seed: u64) -> Graph<W> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut g = empty(n, GraphKind::Undirected);
+    for src in 1..=n {
+        for dst in src+1..=n {
+            g.insert_edge(src, NC(dst, rng.gen_range(costs.clone())));
+        }
+    }
+    g
+}
+
+/// Random directed acyclic graph; nodes are placed in a random topological order
+/// and every edge from an earlier to a later node exists with probability `p`
+pub fn random_dag<W: Weight>(n: usize, p: f64, seed: u64) -> Graph<W> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut order = (1..=n).collect::<Vec<_>>();
+    order.shuffle(&mut rng);
+
+    let mut g = empty(n, GraphKind::Directed);
+    for (i, &src) in order.iter().enumerate() {
+        for &dst in &order[i+1..] {
+            if rng.gen_bool(p) {
+                g.insert_edge(src, NodeType::N(dst));
+            }
+        }
+    }
+    g
+}
+
+/// Barabási–Albert preferential attachment graph; starting from a complete graph of `m + 1` nodes,
+/// every new node attaches to `m` distinct existing nodes chosen with probability proportional to their degree
+/// Returns `None` when `m` is zero or there are not enough nodes, that is, `n <= m`
+pub fn preferential_attachment<W: Weight>(n: usize, m: usize, seed: u64) -> Option<Graph<W>> {
+    if m == 0 || n <= m {
+        return None
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut g = empty(n, GraphKind::Undirected);
+    // every edge adds both its nodes here, hence picking at random is proportional to degree
+    let mut targets = Vec::new();
+    for src in 1..=m+1 {
+        for dst in src+1..=m+1 {
+            g.insert_edge(src, NodeType::N(dst));
+            targets.extend([src, dst]);
+        }
+    }
+    for src in m+2..=n {
+        let mut chosen = HashSet::new();
+        while chosen.len() < m {
+            chosen.insert(*targets.choose(&mut rng).expect("initial graph has edges"));
+        }
+        for dst in chosen {
+            g.insert_edge(src, NodeType::N(dst));
+            targets.extend([src, dst]);
+        }
+    }
+    Some(g)
+}
+
+/// Returns a copy of the graph with every edge cost drawn uniformly from `costs`;
+/// both directions of an undirected edge are given the same cost
+pub fn with_random_costs<W: Weight + SampleUniform>(g: &Graph<W>, costs: Range<W>, seed: u64) -> Graph<W> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    // iterate in node order so the same seed results to the same costs
+    let mut edges = g.edges.iter()
+        .flat_map(|(&src, dst_nodes)| dst_nodes.iter().map(move |&dst| (src, Node::from(dst))))
+        .filter(|&(src, dst)| g.kind == GraphKind::Directed || src <= dst)
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+    edges.dedup();
+
+    edges.into_iter()
+        .fold(Graph { nodes: g.nodes.clone(), ..Graph::new(g.kind) }, |mut out, (src, dst)| {
+            out.insert_edge(src, NC(dst, rng.gen_range(costs.clone())));
+            out
+        })
+}
+
+/// Graph of the given kind holding nodes `1..=n` with no edges
+fn empty<W: Weight>(n: usize, kind: GraphKind) -> Graph<W> {
+    Graph { nodes: (1..=n).collect(), ..Graph::new(kind) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::{path_search::PathSearch, scc::TopologicalSort};
+
+    #[test]
+    fn test_seeded_generators() {
+        let g: Graph = erdos_renyi(50, 0.1, GraphKind::Directed, 7);
+        assert_eq!(g, erdos_renyi(50, 0.1, GraphKind::Directed, 7));
+        assert_ne!(g, erdos_renyi(50, 0.1, GraphKind::Directed, 8));
+        assert_eq!(g.nodes.len(), 50);
+        let g: Graph = erdos_renyi(10, 1.0, GraphKind::Undirected, 7);
+        assert_eq!(g.export_edges().len(), 10 * 9);
+
+        let g: Graph = random_dag(100, 0.2, 3);
+        assert!(g.topological_sort().expect("graph is directed").is_some());
+        assert_eq!(g, random_dag(100, 0.2, 3));
+
+        let g = complete_weighted(8, 1..100, 5);
+        assert!(g.nodes.iter().all(|&node| g.out_degree(node) == 7));
+        assert_eq!(g, with_random_costs(&g, 1..100, 5));
+        assert!(g.path_shortest(1, 8).is_some());
+    }
+    #[test]
+    fn test_structured_generators() {
+        let g: Graph = random_regular(20, 3, 11).expect("20 * 3 is even");
+        assert!(g.nodes.iter().all(|&node| g.out_degree(node) == 3));
+        assert!(random_regular::<Cost>(5, 3, 11).is_none());
+        assert!(random_regular::<Cost>(4, 4, 11).is_none());
+
+        let g: Graph = grid(3, 4, GraphKind::Directed);
+        assert_eq!(g.nodes.len(), 12);
+        assert_eq!(g.export_edges().len(), 3 * 3 + 2 * 4);
+        assert_eq!(g.path_distance(1, 12).map(|(_, d)| d), Some(5));
+
+        let g: Graph = preferential_attachment(100, 2, 13).expect("n > m");
+        // the initial triangle plus m edges per added node
+        assert_eq!(g.export_edges().len(), 2 * (3 + 97 * 2));
+        assert!(g.nodes.iter().all(|&node| g.out_degree(node) >= 2));
+        assert!(preferential_attachment::<Cost>(2, 2, 13).is_none());
+    }
+}
@@ -0,0 +1,181 @@
+use std::{collections::HashMap, hash::Hash};
+use super::{*, path_search::PathSearch, scc::{ConnectedComponents, TopologicalSort}};
+
+/// Minimum spanning tree algorithm, such as `Graph::mst_prim` or `Graph::mst_kruska`
+type MstAlgo<W> = fn(&Graph<W>) -> Result<Option<Graph<W>>, GraphError>;
+
+/// A `Graph` wrapper that interns arbitrary labels, e.g. package names, onto `Node` ids
+/// Labels and ids are kept in a bidirectional map, that is, a `HashMap` for label -> id
+/// and a `Vec` for id -> label, given ids are handed out sequentially from `0`
+/// Results of the underlying `Graph` algorithms can be translated back to labels
+#[derive(Debug, Clone)]
+pub struct LabelledGraph<L, W = Cost> {
+    graph: Graph<W>,
+    ids: HashMap<L, Node>,
+    labels: Vec<L>
+}
+
+impl<L, W> LabelledGraph<L, W>
+    where L: Hash + Eq + Clone, W: Weight {
+
+    /// Construct an empty labelled graph of the given kind
+    pub fn new(kind: GraphKind) -> LabelledGraph<L, W> {
+        LabelledGraph { graph: Graph::new(kind), ids: HashMap::new(), labels: Vec::new() }
+    }
+    /// Construct a labelled graph from a `(src, dst, cost)` edge list
+    pub fn from_edge_list(edge_list: &[(L, L, W)], kind: GraphKind) -> LabelledGraph<L, W> {
+        edge_list.iter()
+            .fold(LabelledGraph::new(kind), |mut g, (src, dst, cost)| {
+                g.add_edge(src.clone(), dst.clone(), Some(*cost));
+                g
+            })
+    }
+    /// The underlying `Graph` with labels replaced by their `Node` ids
+    pub fn graph(&self) -> &Graph<W> {
+        &self.graph
+    }
+    /// Returns the `Node` id of the label, interning the label if it hasn't been seen before
+    pub fn add_node(&mut self, label: L) -> Node {
+        if let Some(&node) = self.ids.get(&label) {
+            return node
+        }
+        let node = self.labels.len();
+        self.labels.push(label.clone());
+        self.ids.insert(label, node);
+        self.graph.nodes.insert(node);
+        node
+    }
+    /// Adds the edge `src -> dst` interning both labels; a `None` cost results to an unweighted edge
+    pub fn add_edge(&mut self, src: L, dst: L, cost: Option<W>) {
+        let src = self.add_node(src);
+        let dst = self.add_node(dst);
+        self.graph.insert_edge(
+            src,
+            cost.map_or(NodeType::N(dst), |cost| NC(dst, cost))
+        );
+    }
+    /// Returns the `Node` id given a label
+    pub fn node(&self, label: &L) -> Option<Node> {
+        self.ids.get(label).copied()
+    }
+    /// Returns the label given a `Node` id
+    pub fn label(&self, node: Node) -> Option<&L> {
+        self.labels.get(node)
+    }
+    /// Translates a sequence of `Node` ids, such as a path, into labels
+    pub fn to_labels(&self, nodes: &[Node]) -> Vec<L> {
+        nodes.iter()
+            .map(|&node| self.label(node)
+                .unwrap_or_else(|| panic!("to_labels(): Node({node}) has no label"))
+                .clone()
+            )
+            .collect()
+    }
+    /// Translates a graph derived from the underlying one, such as a minimum spanning tree,
+    /// into a labelled graph sharing the same labels
+    pub fn to_labelled(&self, graph: Graph<W>) -> LabelledGraph<L, W> {
+        LabelledGraph { graph, ids: self.ids.clone(), labels: self.labels.clone() }
+    }
+    /// Returns all edges as `(src, dst, cost)` labels; unweighted edges have no cost
+    pub fn edges(&self) -> impl Iterator<Item=(&L, &L, Option<W>)> + '_ {
+        self.graph.edges.iter()
+            .flat_map(move |(&src, edges)|
+                edges.iter().map(move |&dst| {
+                    let cost = match dst { NodeType::N(_) => None, NC(_, cost) => Some(cost) };
+                    (&self.labels[src], &self.labels[Node::from(dst)], cost)
+                })
+            )
+    }
+
+    /// Path with the least number of edges between two labels, see `PathSearch::path_distance()`
+    pub fn path_distance(&self, start: &L, goal: &L) -> Option<(Vec<L>, W)> {
+        self.graph.path_distance(self.node(start)?, self.node(goal)?)
+            .map(|(path, cost)| (self.to_labels(&path), cost))
+    }
+    /// Path with the minimum cost between two labels, see `PathSearch::path_shortest()`
+    pub fn path_shortest(&self, start: &L, goal: &L) -> Option<(Vec<L>, W)> {
+        self.graph.path_shortest(self.node(start)?, self.node(goal)?)
+            .map(|(path, cost)| (self.to_labels(&path), cost))
+    }
+    /// A* path with the minimum cost between two labels, see `PathSearch::path_astar()`
+    pub fn path_astar<H: Fn(&L) -> W>(&self, start: &L, goal: &L, heuristic: H) -> Option<(Vec<L>, W)> {
+        self.graph.path_astar(self.node(start)?, self.node(goal)?, |node| heuristic(&self.labels[node]))
+            .map(|(path, cost)| (self.to_labels(&path), cost))
+    }
+    /// Strongly connected components as labels, see `ConnectedComponents::strongly_connected()`
+    pub fn strongly_connected(&self) -> Result<Vec<Vec<L>>, GraphError> {
+        Ok(self.graph.strongly_connected()?
+            .iter()
+            .map(|component| self.to_labels(component))
+            .collect()
+        )
+    }
+    /// Topological order of labels, see `TopologicalSort::topological_sort()`
+    pub fn topological_sort(&self) -> Result<Option<Vec<L>>, GraphError> {
+        Ok(self.graph.topological_sort()?
+            .map(|path| self.to_labels(&path))
+        )
+    }
+    /// Minimum spanning tree given an algorithm such as `Graph::mst_prim` or `Graph::mst_kruska`
+    pub fn min_spanning_tree(&self, algo: MstAlgo<W>) -> Result<Option<LabelledGraph<L, W>>, GraphError> {
+        Ok(algo(&self.graph)?
+            .map(|tree| self.to_labelled(tree))
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_labelled_topological_sort() {
+        let mut g = LabelledGraph::<&str>::new(GraphKind::Directed);
+        g.add_edge("serde", "serde_json", None);
+        g.add_edge("itoa", "serde_json", None);
+        g.add_edge("serde_json", "app", None);
+        g.add_edge("rand", "app", None);
+
+        let order = g.topological_sort().expect("graph is directed").expect("graph is acyclic");
+        let pos = |label| order.iter().position(|l| *l == label).unwrap();
+        assert_eq!(order.len(), 5);
+        assert!(pos("serde") < pos("serde_json"));
+        assert!(pos("itoa") < pos("serde_json"));
+        assert!(pos("serde_json") < pos("app"));
+        assert!(pos("rand") < pos("app"));
+    }
+    #[test]
+    fn test_labelled_path_search() {
+        let g = LabelledGraph::from_edge_list(&[
+            ("a".to_string(), "b".to_string(), 1),
+            ("b".to_string(), "c".to_string(), 1),
+            ("a".to_string(), "c".to_string(), 5),
+        ], GraphKind::Directed);
+
+        assert_eq!(g.node(&"a".to_string()), Some(0));
+        assert_eq!(g.label(2), Some(&"c".to_string()));
+        assert_eq!(g.path_shortest(&"a".to_string(), &"c".to_string()), Some((vec!["a".to_string(), "b".to_string(), "c".to_string()], 2)));
+        assert_eq!(g.path_distance(&"a".to_string(), &"c".to_string()), Some((vec!["a".to_string(), "c".to_string()], 1)));
+        assert_eq!(g.path_shortest(&"a".to_string(), &"z".to_string()), None);
+        assert_eq!(g.path_astar(&"a".to_string(), &"c".to_string(), |_| 0), g.path_shortest(&"a".to_string(), &"c".to_string()));
+    }
+    #[test]
+    fn test_labelled_scc_and_mst() {
+        let g = LabelledGraph::from_edge_list(&[('a', 'b', 1), ('b', 'a', 1), ('b', 'c', 1)], GraphKind::Directed);
+        let mut scc = g.strongly_connected().expect("graph is directed");
+        scc.iter_mut().for_each(|c| c.sort());
+        scc.sort();
+        assert_eq!(scc, vec![vec!['a', 'b'], vec!['c']]);
+
+        let g = LabelledGraph::from_edge_list(&[('a', 'b', 3), ('b', 'c', 1), ('a', 'c', 2)], GraphKind::Undirected);
+        let mst = g.min_spanning_tree(Graph::mst_kruska)
+            .expect("graph is undirected")
+            .expect("graph is connected");
+        let mut edges = mst.edges()
+            .filter(|(src, dst, _)| src < dst)
+            .map(|(&src, &dst, cost)| (src, dst, cost))
+            .collect::<Vec<_>>();
+        edges.sort();
+        assert_eq!(edges, vec![('a', 'c', Some(2)), ('b', 'c', Some(1))]);
+    }
+}
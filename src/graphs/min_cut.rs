@@ -5,14 +5,14 @@ use hashbag::HashBag;
 
 // ANCHOR: graphs_min_cut_super_edges
 #[derive(Debug)]
-pub struct SuperEdges {
-    list: HashMap<Node, HashBag<NodeType>>,
+pub struct SuperEdges<W = Cost> {
+    list: HashMap<Node, HashBag<NodeType<W>>>,
     length: usize
 }
 
-impl SuperEdges {
+impl<W: Weight> SuperEdges<W> {
 
-    pub fn get_random_edge(&self) -> Edge {
+    pub fn get_random_edge(&self) -> Edge<W> {
         let mut idx = thread_rng().gen_range(0..self.length);
 
         let mut iter = self.list.iter();
@@ -159,12 +159,12 @@ impl<'a> Iterator for SuperNodeIter<'a> {
 // ANCHOR_END: graphs_min_cut_super_nodes
 // ANCHOR: graphs_min_cut_super_edges_graph
 /// Helper Graph functions
-impl Graph {
+impl<W: Weight> Graph<W> {
     /// SuperEdges Constructor
-    pub fn get_super_edges(&self) -> SuperEdges {
+    pub fn get_super_edges(&self) -> SuperEdges<W> {
         let mut length = 0;
         let list = self.edges.iter()
-            .map(|(&n,e)| (n, e.iter().copied().collect::<HashBag<NodeType>>())
+            .map(|(&n,e)| (n, e.iter().copied().collect::<HashBag<NodeType<W>>>())
             )
             .inspect(|(_,c)| length += c.len() )
             .collect();
@@ -186,16 +186,16 @@ impl Graph {
 }
 // ANCHOR_END: graphs_min_cut_super_edges_graph
 
-pub trait MinimumCut {
-    fn minimum_cut(&self) -> Option<Graph>;
-    fn contract_graph(&self) -> Option<Graph>;
-    fn get_crossing_edges(&self, src_set:&HashSet<Node>, dst_set:&HashSet<Node>) -> Graph;
+pub trait MinimumCut: Sized {
+    fn minimum_cut(&self) -> Option<Self>;
+    fn contract_graph(&self) -> Option<Self>;
+    fn get_crossing_edges(&self, src_set:&HashSet<Node>, dst_set:&HashSet<Node>) -> Self;
 }
 
-impl MinimumCut for Graph {
+impl<W: Weight> MinimumCut for Graph<W> {
     //noinspection RsExternalLinter
     // ANCHOR: graphs_min_cut
-    fn minimum_cut(&self) -> Option<Graph> {
+    fn minimum_cut(&self) -> Option<Graph<W>> {
 
         // calculate the number of iterations as N*log(N)
         let nodes = self.nodes.len();
@@ -235,7 +235,7 @@ impl MinimumCut for Graph {
     // ANCHOR_END: graphs_min_cut
 
     // ANCHOR: graphs_contraction
-    fn contract_graph(&self) -> Option<Graph> {
+    fn contract_graph(&self) -> Option<Graph<W>> {
 
         if self.edges.is_empty() {
             return None;
@@ -283,7 +283,7 @@ impl MinimumCut for Graph {
 
     // ANCHOR: graphs_crossing
     /// Given two Super Node sets the function returns the crossing edges as a new Graph structure
-    fn get_crossing_edges(&self, src_set: &HashSet<Node>, dst_set: &HashSet<Node>) -> Graph {
+    fn get_crossing_edges(&self, src_set: &HashSet<Node>, dst_set: &HashSet<Node>) -> Graph<W> {
          src_set.iter()
             .map(|src|
                 ( src,
@@ -302,7 +302,7 @@ impl MinimumCut for Graph {
                 (src, set.intersection(dst_set).copied().collect::<HashSet<Node>>())
             )
             .filter(|(_, edges)| !edges.is_empty() )
-            .fold(Graph::new(), |mut out, (&src, edges)| {
+            .fold(Graph::new(GraphKind::Undirected), |mut out, (&src, edges)| {
                 // println!("Node: {node} -> {:?}",edges);
                 // add edges: direction dst -> src
                 edges.iter()
@@ -327,6 +327,7 @@ impl MinimumCut for Graph {
 
 #[cfg(test)]
 mod test {
+    use std::{fs::File, io::BufReader};
     use crate::graphs::Graph;
     use super::*;
 
@@ -373,7 +374,7 @@ mod test {
         ];
 
         for (input, output) in adj_list {
-            let g = Graph::import_edges( &input ).expect("Error: Couldn't load input edges");
+            let g: Graph = Graph::import_edges( &input, GraphKind::Undirected ).expect("Error: Couldn't load input edges");
             let mc = g.minimum_cut();
             assert!(mc.is_some());
             let edges = mc.unwrap().export_edges();
@@ -393,7 +394,8 @@ mod test {
 
         test_data.into_iter()
             .for_each(|(fname, cuts)| {
-                let g = Graph::import_text_graph(fname, ' ', '\0').unwrap_or_else(|| panic!("Cannot open file: {}",fname));
+                let g: Graph = Graph::import_text_graph(BufReader::new(File::open(fname).unwrap_or_else(|e| panic!("Cannot open file: {fname}: {e}"))), ' ', '\0', GraphKind::Undirected)
+                    .unwrap_or_else(|e| panic!("Cannot load graph: {fname}: {e}"));
                 let mc = g.minimum_cut();
                 assert!(mc.is_some());
                 let edges = mc.unwrap().export_edges();
@@ -1,38 +1,156 @@
+pub mod bcc;
+pub mod csr;
+pub mod dot;
+pub mod generate;
+pub mod labelled;
 pub mod min_cut;
 pub mod path_search;
+pub mod sat2;
 pub mod scc;
+pub mod shortest_paths;
+pub mod union_find;
+pub mod weight;
 
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::fmt::{Debug, Error, Formatter};
+use std::fmt::{Debug, Display, Error, Formatter};
+use std::hash::Hash;
+use std::io::BufRead;
 use std::ops::{Index, IndexMut};
+use std::str::FromStr;
 use crate::graphs::NodeType::NC;
+use weight::Weight;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 pub type Node = usize;
+/// Default edge weight; see `Weight` for the supported alternatives
 pub type Cost = i32;
 
+/// Whether a `Graph`'s edges are one-way or two-way
+/// Serializes as `"Directed"` or `"Undirected"`
+#[derive(Debug,Clone,Copy,Hash,Eq,PartialEq,Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GraphKind {
+    /// An edge `src -> dst` can only be traversed from `src` to `dst`
+    #[default]
+    Directed,
+    /// Every edge `src -> dst` is stored along with its mirror `dst -> src`
+    Undirected
+}
+
+/// Errors raised while loading or processing a `Graph`
+#[derive(Debug)]
+pub enum GraphError {
+    /// The underlying reader failed while fetching `line`
+    Io { line: usize, error: std::io::Error },
+    /// A token at `line` and `column` could not be interpreted
+    Parse { line: usize, column: usize, kind: ParseErrorKind },
+    /// The algorithm requires a graph of the `expected` kind
+    KindMismatch { expected: GraphKind, found: GraphKind },
+    /// A cycle of negative total cost makes the minimum cost unbounded;
+    /// every `cycle` node has an edge to the next one, and the last node to the first one
+    NegativeCycle { cycle: Vec<Node> },
+    /// The algorithm requires a directed acyclic graph, however the graph contains a cycle;
+    /// every `cycle` node has an edge to the next one, and the last node to the first one
+    Cycle { cycle: Vec<Node> }
+}
+/// The reason a token failed to parse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// Token is not a valid node id
+    InvalidNode(String),
+    /// Token is not a valid edge cost
+    InvalidCost(String),
+    /// Token is missing its `node,cost` pair
+    MissingCost(String),
+    /// Line ended before all expected tokens were found
+    MissingToken,
+    /// Edge delimiter isn't one of the supported ones
+    UnknownDelimiter(char),
+    /// Token isn't valid at this position
+    UnexpectedToken(String)
+}
+impl GraphError {
+    pub(crate) fn parse(line: usize, column: usize, kind: ParseErrorKind) -> GraphError {
+        GraphError::Parse { line, column, kind }
+    }
+}
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::InvalidNode(txt) => write!(f, "cannot convert ({txt}) to Node"),
+            ParseErrorKind::InvalidCost(txt) => write!(f, "cannot convert ({txt}) to Cost"),
+            ParseErrorKind::MissingCost(txt) => write!(f, "cannot convert ({txt}) into (node, cost)"),
+            ParseErrorKind::MissingToken => write!(f, "unexpected end of line"),
+            ParseErrorKind::UnknownDelimiter(pat) => write!(f, "unknown delimiter ({pat})"),
+            ParseErrorKind::UnexpectedToken(txt) => write!(f, "unexpected token ({txt})"),
+        }
+    }
+}
+impl Display for GraphError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::Io { line, error } => write!(f, "line {line}: cannot read line: {error}"),
+            GraphError::Parse { line, column, kind } => write!(f, "line {line}, column {column}: {kind}"),
+            GraphError::KindMismatch { expected, found } => write!(f, "expected a {expected:?} graph, found a {found:?} one"),
+            GraphError::NegativeCycle { cycle } => write!(f, "negative cost cycle {cycle:?}"),
+            GraphError::Cycle { cycle } => write!(f, "expected an acyclic graph, found cycle {cycle:?}"),
+        }
+    }
+}
+impl std::error::Error for GraphError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GraphError::Io { error, .. } => Some(error),
+            _ => None
+        }
+    }
+}
+
 // ANCHOR: graphs_search_path_utils_Step
+/// Serializes as `{"N": node}` or `{"NC": [node, cost]}`
 #[derive(Debug,Clone,Copy,Hash,Eq,PartialEq)]
-pub enum NodeType {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NodeType<W = Cost> {
     N(Node),
-    NC(Node, Cost)
+    NC(Node, W)
 }
-impl From<NodeType> for Node {
-    fn from(nt: NodeType) -> Self {
+impl<W> From<NodeType<W>> for Node {
+    fn from(nt: NodeType<W>) -> Self {
         match nt { NodeType::N(node)|NC(node, _) => node }
     }
 }
-impl From<Node> for NodeType {
+impl<W> From<Node> for NodeType<W> {
     fn from(value: Node) -> Self {
         NodeType::N(value)
     }
 }
-impl Ord for NodeType {
+impl<W: Copy> NodeType<W> {
+    /// Given the `src` of the edge pointing to this node, returns the destination of the reverse edge,
+    /// that is, `src` with the same cost, if any
+    pub fn reverse(&self, src: Node) -> NodeType<W> {
+        match self {
+            NodeType::N(_) => NodeType::N(src),
+            NC(_, cost) => NC(src, *cost)
+        }
+    }
+}
+impl<W: Weight> NodeType<W> {
+    /// Cost of the edge leading to this node, where an unweighted edge costs `W::ONE`
+    pub fn cost(&self) -> W {
+        match self {
+            NodeType::N(_) => W::ONE,
+            NC(_, cost) => *cost
+        }
+    }
+}
+impl<W: Ord> Ord for NodeType<W> {
     fn cmp(&self, other: &Self) -> Ordering {
         other.partial_cmp(self).unwrap_or_else(|| panic!("Edge::cmp() - cannot compare nodes with type NodeType::N"))
     }
 }
-impl PartialOrd for NodeType {
+impl<W: Ord> PartialOrd for NodeType<W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match other {
             NodeType::N(_) => None,
@@ -45,10 +163,12 @@ impl PartialOrd for NodeType {
 }
 // ANCHOR_END: graphs_search_path_utils_Step
 
+/// Serializes as `[src, dst]` with `dst` being a `NodeType`, e.g. `[1, {"NC": [2, 5]}]`
 #[derive(Clone,Copy,Hash,Eq, PartialEq)]
-pub struct Edge(pub Node, pub NodeType);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Edge<W = Cost>(pub Node, pub NodeType<W>);
 
-impl Debug for Edge {
+impl<W: Debug> Debug for Edge<W> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_tuple("E")
             .field(&self.0)
@@ -57,7 +177,7 @@ impl Debug for Edge {
     }
 }
 
-impl Edge {
+impl<W> Edge<W> {
     // fn has_node(&self, n:Node) -> bool {
     //     self.0 == n || self.1 == n
     // }
@@ -87,16 +207,16 @@ pub enum NodeState {
     Processed
 }
 #[derive(Debug,Clone)]
-pub struct NodeTrack {
-    visited:NodeState,
-    dist:Cost,
-    parent:Option<Node>
+pub struct NodeTrack<W = Cost> {
+    pub visited:NodeState,
+    pub dist:W,
+    pub parent:Option<Node>
 }
-impl NodeTrack {
+impl<W> NodeTrack<W> {
     pub fn visited(&mut self, s:NodeState) -> &mut Self {
         self.visited = s; self
     }
-    pub fn distance(&mut self, d:Cost) -> &mut Self {
+    pub fn distance(&mut self, d:W) -> &mut Self {
         self.dist = d; self
     }
     pub fn parent(&mut self, n:Node) -> &mut Self {
@@ -107,17 +227,17 @@ impl NodeTrack {
     }
 }
 #[derive(Debug)]
-pub struct Tracker {
-    list: HashMap<Node, NodeTrack>
+pub struct Tracker<W = Cost> {
+    list: HashMap<Node, NodeTrack<W>>
 }
-pub trait Tracking {
-    fn extract(&self, start:Node) -> (Vec<Node>, Cost) {
+pub trait Tracking<W = Cost> {
+    fn extract(&self, start:Node) -> (Vec<Node>, W) {
         (self.extract_path(start), self.extract_cost(start))
     }
     fn extract_path(&self, start: Node) -> Vec<Node>;
-    fn extract_cost(&self, start: Node) -> Cost;
+    fn extract_cost(&self, start: Node) -> W;
 }
-impl Tracking for Tracker {
+impl<W: Copy + Debug> Tracking<W> for Tracker<W> {
     fn extract_path(&self, start:Node) -> Vec<Node> {
         let mut path = VecDeque::new();
         // reconstruct the shortest path starting from the target node
@@ -131,27 +251,27 @@ impl Tracking for Tracker {
         }
         path.into()
     }
-    fn extract_cost(&self, start:Node) -> Cost {
+    fn extract_cost(&self, start:Node) -> W {
         self[start].dist
     }
 }
-impl Index<Node> for Tracker {
-    type Output = NodeTrack;
+impl<W: Debug> Index<Node> for Tracker<W> {
+    type Output = NodeTrack<W>;
 
     fn index(&self, index: Node) -> &Self::Output {
         self.list.get(&index).unwrap_or_else(|| panic!("Error: cannot find {index} in tracker {:?}", &self))
     }
 }
-impl IndexMut<Node> for Tracker {
+impl<W: Debug> IndexMut<Node> for Tracker<W> {
     fn index_mut(&mut self, index: Node) -> &mut Self::Output {
         self.list.get_mut(&index).unwrap_or_else(|| panic!("Error: cannot find {index} in tracker"))
     }
 }
 // ANCHOR_END: graphs_search_path_utils_NodeTrack
 // ANCHOR: graphs_search_path_utils_NodeTrack_graph
-impl Graph {
+impl<W> Graph<W> {
 
-    pub fn get_tracker(&self, visited: NodeState, dist: Cost, parent: Option<Node>) -> Tracker {
+    pub fn get_tracker<D: Copy>(&self, visited: NodeState, dist: D, parent: Option<Node>) -> Tracker<D> {
         Tracker {
             list: self.nodes.iter()
                 .fold(HashMap::new(), |mut out, &node| {
@@ -164,34 +284,69 @@ impl Graph {
 }
 // ANCHOR_END: graphs_search_path_utils_NodeTrack_graph
 
-#[derive(PartialEq, Default)]
-pub struct Graph {
-    pub edges: HashMap<Node, HashSet<NodeType>>,
-    pub nodes: HashSet<Node>
+pub struct Graph<W = Cost> {
+    pub edges: HashMap<Node, HashSet<NodeType<W>>>,
+    pub nodes: HashSet<Node>,
+    pub kind: GraphKind
+}
+
+impl<W: Hash + Eq> PartialEq for Graph<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.nodes == other.nodes && self.edges == other.edges
+    }
 }
 
-impl Graph {
-    pub fn new() -> Graph {
-        Graph::default()
+impl<W> Default for Graph<W> {
+    fn default() -> Self {
+        Graph { edges: HashMap::new(), nodes: HashSet::new(), kind: GraphKind::default() }
     }
-    pub fn export_edges(&self) -> HashSet<Edge> {
-        use NodeType::*;
+}
 
+impl<W: Weight> Graph<W> {
+    pub fn new(kind: GraphKind) -> Graph<W> {
+        Graph { kind, ..Graph::default() }
+    }
+    pub fn is_directed(&self) -> bool {
+        self.kind == GraphKind::Directed
+    }
+    /// Returns an error unless the graph is of the given `kind`
+    pub fn ensure_kind(&self, kind: GraphKind) -> Result<(), GraphError> {
+        if self.kind == kind {
+            Ok(())
+        } else {
+            Err(GraphError::KindMismatch { expected: kind, found: self.kind })
+        }
+    }
+    /// Inserts edge `src -> dst` along with its nodes;
+    /// the mirror edge `dst -> src` is also inserted when the graph is undirected
+    pub fn insert_edge(&mut self, src: Node, dst: NodeType<W>) {
+        self.nodes.insert(src);
+        self.nodes.insert(dst.into());
+        self.edges.entry(src)
+            .or_default()
+            .insert(dst);
+        if self.kind == GraphKind::Undirected {
+            self.edges.entry(dst.into())
+                .or_default()
+                .insert(dst.reverse(src));
+        }
+    }
+    /// Returns all edges of the graph; an undirected graph returns both edge directions
+    pub fn export_edges(&self) -> HashSet<Edge<W>> {
         self.edges.iter()
-            .fold( HashSet::<Edge>::new(),|mut edges, (&src, dst_nodes)| {
+            .fold( HashSet::<Edge<W>>::new(),|mut edges, (&src, dst_nodes)| {
                 dst_nodes.iter()
                     .for_each(|&dst_node| {
                         edges.insert(Edge(src, dst_node));
-                        match dst_node {
-                            N(dst) => edges.insert(Edge(dst, N(src))),
-                            NC(dst,cost) => edges.insert(Edge(dst, NC(src, cost)))
-                        };
+                        if self.kind == GraphKind::Undirected {
+                            edges.insert(Edge(dst_node.into(), dst_node.reverse(src)));
+                        }
                     });
                 edges
             })
     }
-    pub fn import_edges( list: &[Vec<Node>] ) -> Result<Self, Error> {
-        let mut graph = Graph::new();
+    pub fn import_edges( list: &[Vec<Node>], kind: GraphKind ) -> Result<Self, Error> {
+        let mut graph = Graph::new(kind);
 
         list.iter().
             map(|edges| {
@@ -200,84 +355,211 @@ impl Graph {
             .for_each(|(src, dst)| {
                 graph.nodes.insert(*src);
                 dst.iter()
-                    .for_each(|dst| {
-                        graph.edges.entry(*src)
-                            .or_default()
-                            .insert((*dst).into());
-                    })
+                    .for_each(|dst| graph.insert_edge(*src, (*dst).into()) )
             });
         Ok(graph)
     }
-    pub fn from_edge_list(edge_list: &[(Node, Node, Cost)]) -> Self {
-        let mut adjacency_list: HashMap<Node, HashSet<NodeType>> = HashMap::new();
-        let mut nodes = HashSet::new();
+    pub fn from_edge_list(edge_list: &[(Node, Node, W)], kind: GraphKind) -> Self {
+        let mut graph = Graph::new(kind);
 
         for &(source, destination, cost) in edge_list.iter() {
-            let destinations = adjacency_list
-                .entry(source)
-                .or_insert_with(HashSet::new);
+            graph.insert_edge(source, NC(destination, cost));
+        }
+        graph
+    }
+    /// Loads a graph from any buffered reader containing an adjacency list, one node per line.
+    /// `node_pat` separates the source node from its edges, while `edge_pat` separates
+    /// a destination node from its cost; use `'\0'` for unweighted edges
+    pub fn import_text_graph<R: BufRead>(reader: R, node_pat: char, edge_pat: char, kind: GraphKind) -> Result<Graph<W>, GraphError> {
+        use ParseErrorKind::*;
 
-            destinations.insert(NC(destination, cost));
+        let mut g = Graph::new(kind);
 
-            nodes.insert(source);
-            nodes.insert(destination);
-        }
+        for (num, line) in reader.lines().enumerate() {
+            // report lines & columns as they appear in a text editor
+            let num = num + 1;
+            let line = line.map_err(|error| GraphError::Io { line: num, error })?;
 
-        Graph {
-            edges: adjacency_list,
-            nodes,
+            let mut part = line.split(node_pat);
+            let txt = part.next().unwrap_or_default();
+            let node = Node::from_str(txt)
+                .map_err(|_| GraphError::parse(num, 1, InvalidNode(txt.to_string())))?;
+            g.nodes.insert(node);
+
+            // column where the next edge starts
+            let mut column = 2 + txt.chars().count();
+            for txt in part {
+                let edge = match edge_pat {
+                    '\0' => NodeType::N(
+                        Node::from_str(txt).map_err(|_| GraphError::parse(num, column, InvalidNode(txt.to_string())))?
+                    ),
+                    ',' => {
+                        let Some((e_str, c_str)) = txt.split_once(edge_pat) else {
+                            return Err(GraphError::parse(num, column, MissingCost(txt.to_string())))
+                        };
+                        NC(
+                            Node::from_str(e_str)
+                                .map_err(|_| GraphError::parse(num, column, InvalidNode(e_str.to_string())))?,
+                            W::from_str(c_str)
+                                .map_err(|_| GraphError::parse(num, column + e_str.chars().count() + 1, InvalidCost(c_str.to_string())))?
+                        )
+                    },
+                    pat => return Err(GraphError::parse(num, column, UnknownDelimiter(pat)))
+                };
+                g.insert_edge(node, edge);
+                column += txt.chars().count() + 1;
+            }
+            // println!("{} -> {:?}",node, g.edges[&node])
+        }
+        Ok(g)
+    }
+    /// Returns the destination nodes of `node`'s outgoing edges
+    pub fn neighbors(&self, node: Node) -> impl Iterator<Item=Node> + '_ {
+        self.edges.get(&node)
+            .into_iter()
+            .flat_map(|edges| edges.iter().map(|&dst| dst.into()))
+    }
+    /// Number of edges leaving `node`; equals `in_degree()` for an undirected graph
+    pub fn out_degree(&self, node: Node) -> usize {
+        self.edges.get(&node).map_or(0, |edges| edges.len())
+    }
+    /// Number of edges arriving at `node`; equals `out_degree()` for an undirected graph
+    pub fn in_degree(&self, node: Node) -> usize {
+        match self.kind {
+            GraphKind::Undirected => self.out_degree(node),
+            GraphKind::Directed => self.edges.values()
+                .flat_map(|edges| edges.iter())
+                .filter(|&&dst| Node::from(dst) == node)
+                .count()
+        }
+    }
+    /// Returns the nodes with an edge arriving at `node`
+    fn sources(&self, node: Node) -> HashSet<Node> {
+        match self.kind {
+            // every edge is mirrored hence the sources are the node's neighbours
+            GraphKind::Undirected => self.neighbors(node).collect(),
+            GraphKind::Directed => self.edges.iter()
+                .filter(|(_, edges)| edges.iter().any(|&dst| Node::from(dst) == node))
+                .map(|(&src, _)| src)
+                .collect()
+        }
+    }
+    /// Removes any `src -> dst` edges, keeping `src` in the graph even if it has no edges left
+    /// Returns the number of edges removed
+    fn remove_edges_to(&mut self, src: Node, dst: Node) -> usize {
+        let Some(edges) = self.edges.get_mut(&src) else { return 0 };
+        let len = edges.len();
+        edges.retain(|&nt| Node::from(nt) != dst);
+        let removed = len - edges.len();
+        if edges.is_empty() {
+            self.edges.remove(&src);
         }
+        removed
     }
-    pub fn import_text_graph(file: &str, node_pat: char, edge_pat: char) -> Option<Graph> {
-        use std::{fs::File, path::Path, io::{BufRead, BufReader}, str::FromStr};
-
-        let mut g = Graph::new();
-        let f = File::open(Path::new(file)).unwrap_or_else(|e| panic!("Could not open {file}: {e}"));
-        let buf = BufReader::new(f);
-
-        buf.lines().into_iter()
-            .enumerate()
-            .map(|(num,line)| (num, line.unwrap_or_else(|e| panic!("Cannot read line:{num} from file: {e}") )))
-            .for_each(|(num,line)| {
-                let mut part = line.split(node_pat);
-                let node = Node::from_str(part.next().unwrap()).unwrap_or_else(|e| panic!("Line {num}: Cannot extract Node from line {e}"));
-                g.nodes.insert(node);
-
-                for txt in part {
-                    let edge = match edge_pat {
-                        '\0' => NodeType::N( Node::from_str(txt).unwrap_or_else(|e| panic!("Line {num}: Cannot convert {txt} to Edge {e}")) ),
-                        ',' =>
-                            if let Some((e_str, c_str)) = txt.split_once(edge_pat) {
-                                NC(
-                                    Node::from_str(e_str).unwrap_or_else(|e| panic!("Line {num}: Cannot convert {e_str} to Edge {e}")),
-                                    Cost::from_str(c_str).unwrap_or_else(|e| panic!("Line {num}: Cannot convert {c_str} to Cost {e}"))
-                                )
-                            } else {
-                                panic!("Cannot convert {txt} into (edge, cost): line {num} ends with a tab ??")
-                            },
-                        pat => panic!("Unknown delimiter:({}) within txt:({txt}",pat)
-                    };
-                    g.edges.entry(node)
-                        .or_default()
-                        .insert(edge);
-                }
-                // println!("{} -> {:?}",node, g.edges[&node])
+    /// Removes edge `src -> dst`, including the mirror edge `dst -> src` when the graph is undirected
+    /// Both nodes remain in the graph; returns `false` if there was no such edge
+    pub fn remove_edge(&mut self, src: Node, dst: Node) -> bool {
+        let removed = self.remove_edges_to(src, dst);
+        if self.kind == GraphKind::Undirected {
+            self.remove_edges_to(dst, src);
+        }
+        removed > 0
+    }
+    /// Removes `node` along with all edges leaving and arriving at it
+    /// Returns `false` if the node isn't part of the graph
+    pub fn remove_node(&mut self, node: Node) -> bool {
+        if !self.nodes.remove(&node) {
+            return false
+        }
+        self.sources(node)
+            .into_iter()
+            .for_each(|src| { self.remove_edges_to(src, node); });
+        self.edges.remove(&node);
+        true
+    }
+    /// Contracts edge `src -> dst` by merging `dst` onto `src`, that is,
+    /// - edges leaving or arriving at `dst` are repointed to `src`, keeping their costs
+    /// - edges between `src` and `dst` are removed rather than turned into self-loops
+    /// - `dst` is removed from the graph
+    ///
+    /// Returns `false` and leaves the graph untouched if there is no `src -> dst` edge
+    pub fn contract_edge(&mut self, src: Node, dst: Node) -> bool {
+        if src == dst || !self.neighbors(src).any(|n| n == dst) {
+            return false
+        }
+        let repoint = |nt: NodeType<W>, node: Node| match nt {
+            NodeType::N(_) => NodeType::N(node),
+            NC(_, cost) => NC(node, cost)
+        };
+        // Fix direction * -> dst
+        for from in self.sources(dst) {
+            let Some(edges) = self.edges.get_mut(&from) else { continue };
+            let incoming = edges.iter().copied().filter(|&nt| Node::from(nt) == dst).collect::<Vec<_>>();
+            incoming.into_iter().for_each(|nt| {
+                edges.remove(&nt);
+                if from != src { edges.insert(repoint(nt, src)); }
             });
-        Some(g)
+            if edges.is_empty() { self.edges.remove(&from); }
+        }
+        // Fix direction dst -> *
+        if let Some(outgoing) = self.edges.remove(&dst) {
+            outgoing.into_iter()
+                .filter(|&nt| Node::from(nt) != src)
+                .for_each(|nt| { self.edges.entry(src).or_default().insert(nt); });
+        }
+        self.nodes.remove(&dst);
+        true
     }
 }
 
 
-impl Clone for Graph {
+impl<W: Clone> Clone for Graph<W> {
     fn clone(&self) -> Self {
         Graph {
             edges: self.edges.clone(),
-            nodes: self.nodes.clone()
+            nodes: self.nodes.clone(),
+            kind: self.kind
         }
     }
 }
 
-impl Debug for Graph {
+/// Serialized form of a `Graph`, with nodes and edges sorted so the output is stable across runs, e.g.
+/// `{"kind": "Undirected", "nodes": [1, 2], "edges": [[1, {"NC": [2, 5]}], [2, {"NC": [1, 5]}]]}`
+/// An undirected graph lists both directions of each edge, as it stores them
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct GraphRepr<W> {
+    kind: GraphKind,
+    nodes: Vec<Node>,
+    edges: Vec<Edge<W>>
+}
+
+#[cfg(feature = "serde")]
+impl<W: Weight + Serialize> Serialize for Graph<W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut nodes = self.nodes.iter().copied().collect::<Vec<_>>();
+        nodes.sort_unstable();
+        let mut edges = self.edges.iter()
+            .flat_map(|(&src, dst_nodes)| dst_nodes.iter().map(move |&dst| Edge(src, dst)))
+            .collect::<Vec<_>>();
+        edges.sort_unstable_by_key(|&Edge(src, dst)| (src, Node::from(dst)));
+        GraphRepr { kind: self.kind, nodes, edges }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, W: Weight + Deserialize<'de>> Deserialize<'de> for Graph<W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let GraphRepr { kind, nodes, edges } = GraphRepr::<W>::deserialize(deserializer)?;
+        let mut g = Graph::new(kind);
+        g.nodes.extend(nodes);
+        // re-inserting keeps both directions of an undirected edge in place, even if one is missing
+        edges.into_iter().for_each(|Edge(src, dst)| g.insert_edge(src, dst));
+        Ok(g)
+    }
+}
+
+impl<W: Debug> Debug for Graph<W> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_map()
             .entries(self.edges.iter())
@@ -287,6 +569,100 @@ impl Debug for Graph {
 
 #[cfg(test)]
 mod test {
-    //use crate::graphs::Graph;
+    use super::*;
+    use ParseErrorKind::*;
+
+    #[test]
+    fn test_import_text_graph_errors() {
+        let test_data = [
+            ("1\t2,3\t4,x\n", ',', (1, 9, InvalidCost("x".to_string()))),
+            ("1\t2,3\n2\ty,3\n", ',', (2, 3, InvalidNode("y".to_string()))),
+            ("1\t2,3\t4\n", ',', (1, 7, MissingCost("4".to_string()))),
+            ("a\t2\n", '\0', (1, 1, InvalidNode("a".to_string()))),
+            ("1\t2\n", ';', (1, 3, UnknownDelimiter(';'))),
+        ];
+        for (input, edge_pat, (line, column, kind)) in test_data {
+            match Graph::<Cost>::import_text_graph(input.as_bytes(), '\t', edge_pat, GraphKind::Directed) {
+                Err(GraphError::Parse { line: l, column: c, kind: k }) => assert_eq!((l, c, k), (line, column, kind)),
+                other => panic!("Expected parse error, got {:?}", other)
+            }
+        }
+        // columns count characters rather than bytes
+        match Graph::<Cost>::import_text_graph("1·2,3·4,x\n".as_bytes(), '·', ',', GraphKind::Directed) {
+            Err(GraphError::Parse { line, column, kind }) => assert_eq!((line, column, kind), (1, 9, InvalidCost("x".to_string()))),
+            other => panic!("Expected parse error, got {:?}", other)
+        }
+    }
+    #[test]
+    fn test_import_text_graph() {
+        let g = Graph::import_text_graph("1\t2,3\t4,5\n2\t1,3\n".as_bytes(), '\t', ',', GraphKind::Directed)
+            .expect("graph couldn't be loaded");
+        assert_eq!(g.nodes, HashSet::from([1, 2, 4]));
+        assert_eq!(g.edges[&1], HashSet::from([NC(2, 3), NC(4, 5)]));
+        assert_eq!(g.edges[&2], HashSet::from([NC(1, 3)]));
+        assert!(!g.edges.contains_key(&4));
+    }
+    #[test]
+    fn test_graph_kind_edges() {
+        let list = [(1, 2, 3), (2, 3, 4)];
+
+        let g = Graph::from_edge_list(&list, GraphKind::Directed);
+        assert_eq!(g.export_edges(), HashSet::from([Edge(1, NC(2, 3)), Edge(2, NC(3, 4))]));
+
+        let g = Graph::from_edge_list(&list, GraphKind::Undirected);
+        assert_eq!(g.edges[&2], HashSet::from([NC(1, 3), NC(3, 4)]));
+        assert_eq!(g.export_edges(), HashSet::from([
+            Edge(1, NC(2, 3)), Edge(2, NC(1, 3)),
+            Edge(2, NC(3, 4)), Edge(3, NC(2, 4))
+        ]));
+        assert!(g.ensure_kind(GraphKind::Undirected).is_ok());
+        assert!(matches!(
+            g.ensure_kind(GraphKind::Directed),
+            Err(GraphError::KindMismatch { expected: GraphKind::Directed, found: GraphKind::Undirected })
+        ));
+    }
+    #[test]
+    fn test_remove_and_contract() {
+        let mut g = Graph::from_edge_list(&[(1, 2, 1), (2, 3, 2), (3, 1, 3), (3, 4, 4)], GraphKind::Directed);
+        assert_eq!(g.neighbors(3).collect::<HashSet<_>>(), HashSet::from([1, 4]));
+        assert_eq!((g.out_degree(3), g.in_degree(3), g.in_degree(4), g.out_degree(4)), (2, 1, 1, 0));
+
+        assert!(g.remove_edge(3, 4));
+        assert!(!g.remove_edge(3, 4));
+        assert!(g.nodes.contains(&4));
+        assert!(g.remove_node(1));
+        assert!(!g.remove_node(1));
+        let mut expected = Graph::from_edge_list(&[(2, 3, 2)], GraphKind::Directed);
+        expected.nodes.insert(4);
+        assert_eq!(g, expected);
 
+        let mut g = Graph::from_edge_list(&[(1, 2, 1), (2, 3, 2), (3, 1, 3), (3, 4, 4)], GraphKind::Undirected);
+        assert_eq!((g.out_degree(3), g.in_degree(3)), (3, 3));
+        assert!(g.remove_node(3));
+        let mut expected = Graph::from_edge_list(&[(1, 2, 1)], GraphKind::Undirected);
+        expected.nodes.insert(4);
+        assert_eq!(g, expected);
+
+        let mut g = Graph::from_edge_list(&[(1, 2, 1), (2, 3, 2), (3, 1, 3), (3, 4, 4)], GraphKind::Undirected);
+        assert!(!g.contract_edge(1, 4));
+        assert!(g.contract_edge(1, 3));
+        assert_eq!(g, Graph::from_edge_list(&[(1, 2, 1), (2, 1, 2), (1, 4, 4)], GraphKind::Undirected));
+
+        let mut g = Graph::from_edge_list(&[(1, 2, 1), (2, 1, 5), (2, 3, 2), (4, 2, 3)], GraphKind::Directed);
+        assert!(g.contract_edge(1, 2));
+        assert_eq!(g, Graph::from_edge_list(&[(1, 3, 2), (4, 1, 3)], GraphKind::Directed));
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_graph_serde() {
+        let g = Graph::from_edge_list(&[(1, 2, 5)], GraphKind::Undirected);
+        let json = serde_json::to_string(&g).expect("graph serializes");
+        assert_eq!(json, r#"{"kind":"Undirected","nodes":[1,2],"edges":[[1,{"NC":[2,5]}],[2,{"NC":[1,5]}]]}"#);
+        assert_eq!(serde_json::from_str::<Graph>(&json).expect("graph deserializes"), g);
+
+        let g = Graph::<weight::OrderedFloat>::import_edges(&[vec![1, 2, 3]], GraphKind::Directed).expect("valid edges");
+        let json = serde_json::to_string(&g).expect("graph serializes");
+        assert_eq!(json, r#"{"kind":"Directed","nodes":[1,2,3],"edges":[[1,{"N":2}],[1,{"N":3}]]}"#);
+        assert_eq!(serde_json::from_str::<Graph<weight::OrderedFloat>>(&json).expect("graph deserializes"), g);
+    }
 }
\ No newline at end of file
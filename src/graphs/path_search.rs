@@ -1,5 +1,6 @@
-use std::collections::{VecDeque, BinaryHeap};
-use super::{*, NodeType::{NC}, NodeState::{Discovered, Undiscovered}};
+use std::collections::{VecDeque, BinaryHeap, HashMap};
+use crate::hash_heap::indexed::IndexedHeap;
+use super::{*, NodeType::{NC}, NodeState::{Discovered, Processed, Undiscovered}};
 
 // ANCHOR: graphs_search_bfs_abstraction
 /// Breadth First Search abstraction, that can be used to find shortest distance, lowest cost paths, etc
@@ -9,9 +10,49 @@ use super::{*, NodeType::{NC}, NodeState::{Discovered, Undiscovered}};
 /// - Queue-to-Node and vice versa step fn()
 /// - Node pre/post-processing step fn()
 /// - Edge pre-processing step fn()
+/// - abort search fn()
 /// - Path return fn()
 /// - node state fn()
-trait BFSearch {
+///
+/// Implement it on your own state structure to write custom traversals, for example, reachability within a number of hops
+/// ```
+/// use std::collections::VecDeque;
+/// use csx3::graphs::{*, path_search::BFSearch};
+///
+/// struct Reach { tracker: Tracker<usize>, queue: VecDeque<Node>, max_hops: usize }
+///
+/// impl BFSearch<Cost> for Reach {
+///     type Output = Vec<Node>;
+///     type QueueItem = Node;
+///
+///     fn initiate(&mut self, start: Node) -> &mut Self {
+///         self.tracker[start].visited(NodeState::Discovered);
+///         self.queue.push_back(start);
+///         self
+///     }
+///     fn pop(&mut self) -> Option<Node> { self.queue.pop_front() }
+///     fn node_from_queued(&self, node: &Node) -> Node { *node }
+///     fn is_discovered(&self, node: NodeType) -> bool { self.tracker[node.into()].is_discovered() }
+///     /// prune any edge leading further than `max_hops` away from the start
+///     fn pre_process_edge(&mut self, src: Node, dst: NodeType) -> bool {
+///         let hops = self.tracker[src].dist + 1;
+///         if hops > self.max_hops { return false }
+///         self.tracker[dst.into()].visited(NodeState::Discovered).distance(hops).parent(src);
+///         true
+///     }
+///     fn node_to_queued(&self, node: Node) -> Node { node }
+///     fn push(&mut self, node: Node) { self.queue.push_back(node) }
+///     fn extract_path(&self, goal: Node) -> Vec<Node> { self.tracker.extract_path(goal) }
+/// }
+///
+/// let g: Graph = Graph::import_edges(&[vec![1, 2], vec![2, 3], vec![3, 4]], GraphKind::Directed).unwrap();
+/// let mut reach = Reach { tracker: g.get_tracker(NodeState::Undiscovered, 0, None), queue: VecDeque::new(), max_hops: 2 };
+/// assert_eq!(reach.path_search(&g, 1, 3), Some(vec![1, 2, 3]));
+///
+/// let mut reach = Reach { tracker: g.get_tracker(NodeState::Undiscovered, 0, None), queue: VecDeque::new(), max_hops: 2 };
+/// assert_eq!(reach.path_search(&g, 1, 4), None);
+/// ```
+pub trait BFSearch<W: Weight> {
     type Output;
     type QueueItem;
 
@@ -31,12 +72,16 @@ trait BFSearch {
     /// Process node after all edges have been processes and pushed in the queue
     fn post_process_node(&mut self, _node: Node) { }
 
+    /// Abort the search, returning `None`
+    /// default implementation never aborts otherwise you have to override
+    fn abort(&self) -> bool { false }
+
     /// Has the node been Discovered ?
-    fn is_discovered(&self, _node: NodeType) -> bool;
+    fn is_discovered(&self, _node: NodeType<W>) -> bool;
 
     /// Process the Edge node and
     /// return 'true' to proceed with push or 'false' to skip the edge node
-    fn pre_process_edge(&mut self, src: Node, dst: NodeType) -> bool;
+    fn pre_process_edge(&mut self, src: Node, dst: NodeType<W>) -> bool;
 
     /// Construct a Queued Item from the Node
     fn node_to_queued(&self, node: Node) -> Self::QueueItem;
@@ -48,7 +93,13 @@ trait BFSearch {
     fn extract_path(&self, start: Node) -> Self::Output;
 
     /// Path Search Implementation
-    fn path_search(&mut self, g: &Graph, start: Node, goal:Node) -> Option<Self::Output> {
+    fn path_search(&mut self, g: &Graph<W>, start: Node, goal:Node) -> Option<Self::Output> {
+        self.traverse(g, start, Some(goal))
+    }
+
+    /// Search from `start` until `goal` is reached, or with no `goal`, until all nodes reachable from `start`
+    /// have been processed, in which case `None` is returned and the outcome is left in the state structure
+    fn traverse(&mut self, g: &Graph<W>, start: Node, goal: Option<Node>) -> Option<Self::Output> {
         // initiate BFSearch given a start node
         self.initiate(start);
         // until no items left for processing
@@ -57,9 +108,11 @@ trait BFSearch {
             let src = self.node_from_queued(&qt);
             // pre-process and if false abandon and proceed to next item
             if !self.pre_process_node(src) { continue };
+            // stop searching if requested so
+            if self.abort() { return None };
             // if we have reached our goal return the path
-            if src == goal {
-                return Some(self.extract_path(goal))
+            if goal == Some(src) {
+                return Some(self.extract_path(src))
             }
             // given graph's edges
             // get src node's edges and per their NodeType
@@ -74,40 +127,141 @@ trait BFSearch {
                             self.push(self.node_to_queued(dst.into()))
                         }
                     });
-                // Process node after edges have been discovered and pushed for further processing
-                self.post_process_node(src);
             }
+            // Process node after edges have been discovered and pushed for further processing
+            self.post_process_node(src);
         }
         None
     }
 }
 // ANCHOR_END: graphs_search_bfs_abstraction
-pub trait PathSearch {
-    fn path_distance(&self, start:Node, goal:Node) -> Option<(Vec<Node>, Cost)>;
-    fn path_shortest(&self, start: Node, goal: Node) -> Option<(Vec<Node>, Cost)>;
+// ANCHOR: graphs_search_path_min_cost
+/// Structure for maintaining processing state while searching the graph for minimum cost paths
+/// Shared by `path_shortest()`, `path_bidirectional()` and `ShortestPaths::shortest_path_tree()`
+pub(crate) struct PSState<W> {
+    pub(crate) tracker: Tracker<W>,
+    queue: IndexedHeap<Node, W>
+}
+
+/// State Constructor from a given Graph and related shortest path initiation requirements
+impl<W: Weight> PSState<W> {
+    pub(crate) fn new(g:&Graph<W>) -> PSState<W> {
+        PSState {
+            // reset all node costs to MAX value with no path-parent nodes
+            tracker: g.get_tracker(Undiscovered, W::MAX, None),
+            // We are using an indexed min-heap queue in order to always have first in the queue
+            // the node with lowest cost to explore next, while each node is queued only once
+            queue: IndexedHeap::new()
+        }
+    }
+    /// lowest cost in the queue, hence no node can be settled at a lower cost from now on
+    fn top(&self) -> Option<W> {
+        self.queue.peek().map(|(_, &cost)| cost)
+    }
 }
 
-impl PathSearch for Graph {
+/// Implementation of Path Search abstraction
+/// Reached nodes are `Discovered` while nodes whose minimum cost is final are `Processed`
+impl<W: Weight> BFSearch<W> for PSState<W> {
+    type Output = (Vec<Node>,W);
+    type QueueItem = NodeType<W>;
+
+    /// Processing of starting node
+    fn initiate(&mut self, start: Node) -> &mut Self {
+        // set cost at start node to zero with no parent node
+        self.tracker[start].visited(Discovered).distance(W::ZERO);
+        // push start node in the indexed heap queue
+        self.queue.push(start, W::ZERO);
+        self
+    }
+
+    /// get the element with the lowest cost from the queue
+    fn pop(&mut self) -> Option<Self::QueueItem> {
+        self.queue.pop().map(|(node, cost)| NC(node, cost))
+    }
+
+    /// extract node from the queued item retrieved
+    fn node_from_queued(&self, qt: &Self::QueueItem) -> Node {
+        (*qt).into()
+    }
+
+    /// Process current node after all edges have been discovered and marked for processing
+    fn post_process_node(&mut self, node: Node) {
+        self.tracker[node].visited(Processed);
+    }
+
+    /// has the given node's minimum cost been found ?
+    fn is_discovered(&self, node: NodeType<W>) -> bool { self.tracker[node.into()].visited == Processed }
+
+    /// Process given edge and return `true` to proceed or `false` to abandon further edge processing
+    fn pre_process_edge(&mut self, src:Node, dst: NodeType<W>) -> bool {
+        // calc the new path cost to edge; an unweighted edge costs `W::ONE`
+        let edge_cost = self.tracker[src].dist + dst.cost();
+        let dst = dst.into();
+        // if new cost is better than previously found
+        if edge_cost > self.tracker[dst].dist  {
+            // Do no process this edge any further
+            false
+        }
+        else {
+            // set the new lower cost @node along with related parent Node
+            self.tracker[dst].visited(Discovered).distance(edge_cost).parent(src);
+            // and ensure the edge is processed further
+            true
+        }
+    }
+
+    /// Construct the item to be queued, that is, (Node,Cost)
+    fn node_to_queued(&self, node: Node) -> Self::QueueItem {
+        NC(node, self.tracker[node].dist )
+    }
+
+    /// Push (Node,Cost) into the queue, or lower the cost of the node if it's already queued
+    fn push(&mut self, item: Self::QueueItem) {
+        let NC(node, cost) = item else { panic!("push(): Must use NodeType::NC") };
+        if !self.queue.decrease_key(&node, cost) && !self.queue.contains(&node) {
+            self.queue.push(node, cost);
+        }
+    }
+
+    /// Get search path discovered so far
+    fn extract_path(&self, start: Node) -> Self::Output { self.tracker.extract(start) }
+}
+// ANCHOR_END: graphs_search_path_min_cost
+
+pub trait PathSearch<W = Cost> {
+    fn path_distance(&self, start:Node, goal:Node) -> Option<(Vec<Node>, W)>;
+    fn path_shortest(&self, start: Node, goal: Node) -> Option<(Vec<Node>, W)>;
+    /// A* search for the path with the minimum cost, where `heuristic` estimates the remaining cost from a node to the goal
+    /// The path returned is the cheapest one as long as the heuristic never overestimates, e.g. the manhattan distance on a grid;
+    /// a node is expanded again whenever a cheaper path to it is found, hence the heuristic need not be consistent
+    fn path_astar<H: Fn(Node) -> W>(&self, start: Node, goal: Node, heuristic: H) -> Option<(Vec<Node>, W)>;
+    /// Bidirectional Dijkstra search for the path with the minimum cost, that is, one search runs forward from `start`
+    /// while another runs backward from `goal` over the reversed edges, until the two meet
+    fn path_bidirectional(&self, start: Node, goal: Node) -> Option<(Vec<Node>, W)>;
+}
+
+impl<W: Weight> PathSearch<W> for Graph<W> {
 
     // ANCHOR: graphs_search_path_shortest
-    fn path_distance(&self, start:Node, goal:Node) -> Option<(Vec<Node>, Cost)> {
+    fn path_distance(&self, start:Node, goal:Node) -> Option<(Vec<Node>, W)> {
         /// Structure for maintaining processing state while processing the graph
-        struct PDState {
-            tracker: Tracker,
+        struct PDState<W> {
+            tracker: Tracker<W>,
             queue: VecDeque<Node>
         }
         /// State Constructor from a given Graph and related the initiation requirements for the algo
-        impl PDState {
-            fn new(g: &Graph) -> PDState {
+        impl<W: Weight> PDState<W> {
+            fn new(g: &Graph<W>) -> PDState<W> {
                 PDState {
-                    tracker: g.get_tracker(Undiscovered, 0, None),
+                    tracker: g.get_tracker(Undiscovered, W::ZERO, None),
                     queue: VecDeque::<Node>::new()
                 }
             }
         }
         /// Implementation of Path Search abstraction
-        impl BFSearch for PDState {
-            type Output = (Vec<Node>, Cost);
+        impl<W: Weight> BFSearch<W> for PDState<W> {
+            type Output = (Vec<Node>, W);
             type QueueItem = Node;
 
             /// Initiate search by pushing starting node and mark it as Discovered
@@ -124,11 +278,11 @@ impl PathSearch for Graph {
             fn node_from_queued(&self, node: &Self::QueueItem) -> Node { *node }
 
             /// Has it seen before ?
-            fn is_discovered(&self, node: NodeType) -> bool { self.tracker[node.into()].is_discovered() }
+            fn is_discovered(&self, node: NodeType<W>) -> bool { self.tracker[node.into()].is_discovered() }
 
             /// Process Edge before pushing it at the end of the queue
-            fn pre_process_edge(&mut self, src: Node, dst: NodeType) -> bool {
-                let level = self.tracker[src].dist + 1;
+            fn pre_process_edge(&mut self, src: Node, dst: NodeType<W>) -> bool {
+                let level = self.tracker[src].dist + W::ONE;
                 // mark visited, calculate distance & store parent for distance
                 self.tracker[dst.into()].visited(Discovered)
                     .distance(level)
@@ -150,104 +304,395 @@ impl PathSearch for Graph {
         PDState::new(self).path_search(self, start, goal )
     }
     // ANCHOR_END: graphs_search_path_shortest
-    // ANCHOR: graphs_search_path_min_cost
-    fn path_shortest(&self, start: Node, goal: Node) -> Option<(Vec<Node>, Cost)> {
+    fn path_shortest(&self, start: Node, goal: Node) -> Option<(Vec<Node>, W)> {
+        // Construct the state structure and search for a path that exists between start -> goal
+        PSState::new(self).path_search(self,start,goal)
+    }
+    // ANCHOR: graphs_search_path_astar
+    fn path_astar<H: Fn(Node) -> W>(&self, start: Node, goal: Node, heuristic: H) -> Option<(Vec<Node>, W)> {
         /// Structure for maintaining processing state while processing the graph
-        struct PSState {
-            tracker: Tracker,
-            queue: BinaryHeap<NodeType>
-        }
-
-        /// State Constructor from a given Graph and related shortest path initiation requirements
-        impl PSState {
-            fn new(g:&Graph) -> PSState {
-                PSState {
-                    // reset all node costs to MAX value with no path-parent nodes
-                    tracker: g.get_tracker(Undiscovered, Cost::MAX, None),
-                    // We are using a BinaryHeap queue in order to always have first in the queue
-                    // the node with lowest cost to explore next
-                    queue: BinaryHeap::new()
-                }
-            }
+        struct AState<W, H> {
+            tracker: Tracker<W>,
+            queue: BinaryHeap<NodeType<W>>,
+            heuristic: H
         }
 
         /// Implementation of Path Search abstraction
-        impl BFSearch for PSState {
-            type Output = (Vec<Node>,Cost);
-            type QueueItem = NodeType;
+        /// Same as the shortest path search, with the difference that the queue is prioritised by
+        /// the cost so far plus the estimated cost to the goal, hence nodes closer to the goal are expanded first
+        impl<W: Weight, H: Fn(Node) -> W> BFSearch<W> for AState<W, H> {
+            type Output = (Vec<Node>,W);
+            type QueueItem = NodeType<W>;
 
-            /// Processing of starting node
+            /// set cost at start node to zero with no parent node and push it in the queue
             fn initiate(&mut self, start: Node) -> &mut Self {
-                // set cost at start node to zero with no parent node
-                self.tracker[start].distance(0);
-                // push start node in the BinaryHeap queue
-                self.queue.push(NC(start,0));
+                self.tracker[start].distance(W::ZERO);
+                self.queue.push(self.node_to_queued(start));
                 self
             }
 
-            /// get the element with the lowest cost from the queue
-            fn pop(&mut self) -> Option<Self::QueueItem> { self.queue.pop() }
+            /// get the element with the lowest estimated cost from the queue, skipping stale entries,
+            /// that is, those queued before a cheaper path to the node was found
+            fn pop(&mut self) -> Option<Self::QueueItem> {
+                while let Some(qt) = self.queue.pop() {
+                    if qt == self.node_to_queued(qt.into()) { return Some(qt) }
+                }
+                None
+            }
 
             /// extract node from the queued item retrieved
-            fn node_from_queued(&self, qt: &Self::QueueItem) -> Node {
-                (*qt).into()
+            fn node_from_queued(&self, qt: &Self::QueueItem) -> Node { (*qt).into() }
+
+            /// an inconsistent heuristic may expand a node before its cheapest path is found,
+            /// hence no node is ever closed; instead it's reopened whenever its cost is lowered
+            fn is_discovered(&self, _node: NodeType<W>) -> bool { false }
+
+            /// proceed only if the edge results to a lower cost path to `dst`
+            fn pre_process_edge(&mut self, src: Node, dst: NodeType<W>) -> bool {
+                let edge_cost = self.tracker[src].dist + dst.cost();
+                if edge_cost >= self.tracker[dst.into()].dist {
+                    false
+                } else {
+                    self.tracker[dst.into()].distance(edge_cost).parent(src);
+                    true
+                }
             }
 
-            /// Process current node after all edges have been discovered and marked for processing
-            fn post_process_node(&mut self, node: Node) {
-                self.tracker[node].visited(Discovered);
+            /// Construct the item to be queued, that is, (Node, cost so far + estimated cost to goal)
+            fn node_to_queued(&self, node: Node) -> Self::QueueItem {
+                NC(node, self.tracker[node].dist + (self.heuristic)(node))
             }
 
-            /// has the given node been seen before ?
-            fn is_discovered(&self, node: NodeType) -> bool { self.tracker[node.into()].is_discovered() }
-
-            /// Process given edge and return `true` to proceed or `false` to abandon further edge processing
-            fn pre_process_edge(&mut self, src:Node, dst: NodeType) -> bool {
-                if let NC(dst, cost) = dst {
-                    // calc the new path cost to edge
-                    let edge_cost = self.tracker[src].dist + cost;
-                    // if new cost is better than previously found
-                    if edge_cost > self.tracker[dst].dist  {
-                        // Do no process this edge any further
-                        false
+            /// Push into (Node,Cost) into the queue
+            fn push(&mut self, item: Self::QueueItem) { self.queue.push(item) }
+
+            /// Get search path discovered so far
+            fn extract_path(&self, start: Node) -> Self::Output { self.tracker.extract(start) }
+        }
+
+        AState {
+            tracker: self.get_tracker(Undiscovered, W::MAX, None),
+            queue: BinaryHeap::new(),
+            heuristic
+        }.path_search(self, start, goal)
+    }
+    // ANCHOR_END: graphs_search_path_astar
+    // ANCHOR: graphs_search_path_bidirectional
+    fn path_bidirectional(&self, start: Node, goal: Node) -> Option<(Vec<Node>, W)> {
+        /// Processing state of one of the two searches, that is, a minimum cost search over the side's graph
+        struct Side<'a, W> {
+            graph: &'a Graph<W>,
+            state: PSState<W>
+        }
+        impl<'a, W: Weight> Side<'a, W> {
+            fn new(graph: &'a Graph<W>, start: Node) -> Side<'a, W> {
+                let mut state = PSState::new(graph);
+                state.initiate(start);
+                Side { graph, state }
+            }
+            /// settle the lowest cost node, relax its edges and return the best `(cost, meeting node)`
+            /// found among the nodes already reached by the `other` search
+            fn step(&mut self, other: &Side<W>, mut best: Option<(W, Node)>) -> Option<(W, Node)> {
+                let Some(qt) = self.state.pop() else { return best };
+                let src = self.state.node_from_queued(&qt);
+
+                for &dst in self.graph.edges.get(&src).into_iter().flatten() {
+                    if !self.state.is_discovered(dst) && self.state.pre_process_edge(src, dst) {
+                        self.state.push(self.state.node_to_queued(dst.into()));
                     }
-                    else {
-                        // set the new lower cost @node along with related parent Node
-                        self.tracker[dst].distance(edge_cost).parent(src);
-                        // and ensure the edge is processed further
-                        true
+                    // a path through `src -> dst` joins the two searches
+                    let node = dst.into();
+                    if other.state.tracker[node].is_discovered() {
+                        let path_cost = self.state.tracker[src].dist + dst.cost() + other.state.tracker[node].dist;
+                        if best.is_none_or(|(best_cost, _)| path_cost < best_cost) {
+                            best = Some((path_cost, node));
+                        }
                     }
                 }
-                else {
-                    // somehow we got the wrong NodeType here
-                    panic!("pre_process_edge(): Must use NodeType::NC")
-                }
+                self.state.post_process_node(src);
+                best
             }
+        }
 
-            /// Construct the item to be queued, that is, (Node,Cost)
-            fn node_to_queued(&self, node: Node) -> Self::QueueItem {
-                NC(node, self.tracker[node].dist )
+        if start == goal {
+            return Some((vec![start], W::ZERO))
+        }
+        let transposed = self.transpose();
+        let mut forward = Side::new(self, start);
+        let mut backward = Side::new(&transposed, goal);
+        let mut best = None;
+
+        // expand the side with the lowest cost in its queue, until no shorter path can be found,
+        // that is, the two lowest costs add up to more than the best path found so far
+        while let (Some(f), Some(b)) = (forward.state.top(), backward.state.top()) {
+            if best.is_some_and(|(cost, _)| f + b >= cost) { break }
+            best = if f <= b {
+                forward.step(&backward, best)
+            } else {
+                backward.step(&forward, best)
+            };
+        }
+
+        // join the forward path start -> meet with the reversed backward path goal -> meet
+        let (cost, meet) = best?;
+        let mut path = forward.state.tracker.extract_path(meet);
+        let mut back = backward.state.tracker.extract_path(meet);
+        back.pop();
+        path.extend(back.into_iter().rev());
+        Some((path, cost))
+    }
+    // ANCHOR_END: graphs_search_path_bidirectional
+}
+
+/// Predicate over the edge `src -> dst`, returning `true` when the edge can be followed
+type EdgeFilter<'a, W> = Box<dyn Fn(Node, NodeType<W>) -> bool + 'a>;
+
+/// Restrictions a path must satisfy, such as nodes that are out of service or edges over capacity
+/// Constraints are built up by chaining, e.g. `PathConstraints::new().exclude([3, 4]).max_hops(5)`,
+/// while an unconstrained search is the same as `PathConstraints::new()`
+pub struct PathConstraints<'a, W = Cost> {
+    excluded: HashSet<Node>,
+    edge_filter: Option<EdgeFilter<'a, W>>,
+    max_hops: Option<usize>,
+    max_cost: Option<W>
+}
+
+impl<'a, W: Weight> PathConstraints<'a, W> {
+    /// Constraints that allow any path
+    pub fn new() -> PathConstraints<'a, W> {
+        PathConstraints { excluded: HashSet::new(), edge_filter: None, max_hops: None, max_cost: None }
+    }
+    /// Nodes the path must not pass through, including the start and goal nodes
+    pub fn exclude<I: IntoIterator<Item=Node>>(mut self, nodes: I) -> Self {
+        self.excluded.extend(nodes);
+        self
+    }
+    /// Edges `src -> dst` the path may follow, that is, those the predicate returns `true` for
+    pub fn edge_filter<F: Fn(Node, NodeType<W>) -> bool + 'a>(mut self, filter: F) -> Self {
+        self.edge_filter = Some(Box::new(filter));
+        self
+    }
+    /// Maximum number of edges in the path
+    pub fn max_hops(mut self, hops: usize) -> Self {
+        self.max_hops = Some(hops);
+        self
+    }
+    /// Maximum total cost of the path; an unweighted edge costs `W::ONE`, see `NodeType::cost()`
+    pub fn max_cost(mut self, cost: W) -> Self {
+        self.max_cost = Some(cost);
+        self
+    }
+    /// Can a path of `hops` edges and `cost` total cost end with the edge `src -> dst` ?
+    fn allows(&self, src: Node, dst: NodeType<W>, hops: usize, cost: W) -> bool {
+        !self.excluded.contains(&dst.into())
+            && self.max_hops.is_none_or(|max| hops <= max)
+            && self.max_cost.is_none_or(|max| cost <= max)
+            && self.edge_filter.as_ref().is_none_or(|filter| filter(src, dst))
+    }
+}
+
+impl<W: Weight> Default for PathConstraints<'_, W> {
+    fn default() -> Self { PathConstraints::new() }
+}
+
+/// Path searches that skip any edge violating the given `PathConstraints`, without altering the graph
+/// A path meeting the hops or cost limit may be neither the fewest edges nor the minimum cost path to each of its nodes,
+/// hence both searches keep a label per `(node, hops)` pair, that is, the cost & parent node of a path reaching `node` in `hops` edges,
+/// and drop a label only when another path reaches the same node with no more edges at no higher cost
+/// Edge costs must not be negative, same as with `path_shortest()`
+pub trait ConstrainedPathSearch<W = Cost> {
+    /// Path with the least number of edges that satisfies the constraints, along with its number of edges
+    fn path_distance_constrained(&self, start: Node, goal: Node, constraints: &PathConstraints<W>) -> Option<(Vec<Node>, W)>;
+    /// Path with the minimum cost that satisfies the constraints, along with its cost
+    fn path_shortest_constrained(&self, start: Node, goal: Node, constraints: &PathConstraints<W>) -> Option<(Vec<Node>, W)>;
+}
+
+/// Cost and parent node of the path reaching a node in a number of edges, per `(node, hops)` pair
+type Labels<W> = HashMap<(Node, usize), (W, Option<Node>)>;
+
+/// Walks back the parent nodes from the `(node, hops)` label, one edge at a time, and returns the path in order
+fn extract_label<W>(labels: &Labels<W>, (mut node, mut hops): (Node, usize)) -> Vec<Node> {
+    let mut path = vec![node];
+    while let Some(parent) = labels[&(node, hops)].1 {
+        (node, hops) = (parent, hops - 1);
+        path.push(node);
+    }
+    path.reverse();
+    path
+}
+
+impl<W: Weight> ConstrainedPathSearch<W> for Graph<W> {
+    // ANCHOR: graphs_search_path_constrained
+    fn path_distance_constrained(&self, start: Node, goal: Node, constraints: &PathConstraints<W>) -> Option<(Vec<Node>, W)> {
+        /// Same as the `path_distance()` state, with the queue holding `(node, hops)` labels in increasing hops order
+        struct CDState<'a, 'b, W> {
+            labels: Labels<W>,
+            /// lowest cost found per node, among labels with no more edges than those being created
+            best: HashMap<Node, W>,
+            queue: VecDeque<(Node, usize)>,
+            /// label being processed
+            current: (Node, usize),
+            constraints: &'a PathConstraints<'b, W>
+        }
+
+        impl<W: Weight> BFSearch<W> for CDState<'_, '_, W> {
+            type Output = (Vec<Node>, W);
+            type QueueItem = (Node, usize);
+
+            /// Initiate search by pushing the starting node with no edges at zero cost
+            fn initiate(&mut self, start: Node) -> &mut Self {
+                self.labels.insert((start, 0), (W::ZERO, None));
+                self.best.insert(start, W::ZERO);
+                self.queue.push_back((start, 0));
+                self
             }
 
-            /// Push into (Node,Cost) into the queue
-            fn push(&mut self, item: Self::QueueItem) { self.queue.push(item) }
+            /// Get the first label from the start of the queue and keep it as the one being processed
+            fn pop(&mut self) -> Option<Self::QueueItem> {
+                let label = self.queue.pop_front()?;
+                self.current = label;
+                Some(label)
+            }
 
-            /// Get search path discovered so far
-            fn extract_path(&self, start: Node) -> Self::Output { self.tracker.extract(start) }
+            /// extract Node from the queued label
+            fn node_from_queued(&self, &(node, _): &Self::QueueItem) -> Node { node }
+
+            /// A node can be reached by several labels, hence edges are always checked
+            fn is_discovered(&self, _node: NodeType<W>) -> bool { false }
+
+            /// Skip the edge if it violates the constraints, or if a path with no more edges reaches `dst` at no higher cost;
+            /// a label found again within the same number of edges takes the lower cost without being queued twice
+            fn pre_process_edge(&mut self, src: Node, dst: NodeType<W>) -> bool {
+                let hops = self.current.1 + 1;
+                let cost = self.labels[&self.current].0 + dst.cost();
+                let node = dst.into();
+                if !self.constraints.allows(src, dst, hops, cost) || self.best.get(&node).is_some_and(|&best| best <= cost) {
+                    return false
+                }
+                self.best.insert(node, cost);
+                self.labels.insert((node, hops), (cost, Some(src))).is_none()
+            }
+
+            /// Construct the label of the node reached through the label being processed
+            fn node_to_queued(&self, node: Node) -> Self::QueueItem { (node, self.current.1 + 1) }
+
+            /// Push label at the end of the queue
+            fn push(&mut self, item: Self::QueueItem) { self.queue.push_back(item) }
+
+            /// Extract the path of the label being processed, along with its number of edges
+            fn extract_path(&self, _goal: Node) -> Self::Output {
+                let path = extract_label(&self.labels, self.current);
+                let hops = path.iter().skip(1).fold(W::ZERO, |hops, _| hops + W::ONE);
+                (path, hops)
+            }
         }
 
-        // Construct the state structure and search for a path that exists between start -> goal
-        PSState::new(self).path_search(self,start,goal)
+        if constraints.excluded.contains(&start) { return None }
+        CDState {
+            labels: HashMap::new(),
+            best: HashMap::new(),
+            queue: VecDeque::new(),
+            current: (start, 0),
+            constraints
+        }.path_search(self, start, goal)
+    }
+    fn path_shortest_constrained(&self, start: Node, goal: Node, constraints: &PathConstraints<W>) -> Option<(Vec<Node>, W)> {
+        /// Same as the `path_shortest()` state, with the queue holding `(node, hops)` labels in increasing cost order
+        struct CSState<'a, 'b, W> {
+            labels: Labels<W>,
+            /// fewest edges per node among the labels processed so far, all of which cost no more than those still queued
+            settled: HashMap<Node, usize>,
+            queue: IndexedHeap<(Node, usize), W>,
+            /// label being processed
+            current: (Node, usize),
+            constraints: &'a PathConstraints<'b, W>
+        }
+
+        impl<W: Weight> BFSearch<W> for CSState<'_, '_, W> {
+            type Output = (Vec<Node>, W);
+            type QueueItem = (Node, usize);
+
+            /// set cost at start node to zero with no edges and push it in the queue
+            fn initiate(&mut self, start: Node) -> &mut Self {
+                self.labels.insert((start, 0), (W::ZERO, None));
+                self.queue.push((start, 0), W::ZERO);
+                self
+            }
+
+            /// get the label with the lowest cost from the queue and keep it as the one being processed
+            fn pop(&mut self) -> Option<Self::QueueItem> {
+                let (label, _) = self.queue.pop()?;
+                self.current = label;
+                Some(label)
+            }
+
+            /// extract node from the queued label
+            fn node_from_queued(&self, &(node, _): &Self::QueueItem) -> Node { node }
+
+            /// skip the label if the node has already been processed at no higher cost with no more edges
+            fn pre_process_node(&mut self, node: Node) -> bool {
+                let hops = self.current.1;
+                if self.settled.get(&node).is_some_and(|&settled| settled <= hops) { return false }
+                self.settled.insert(node, hops);
+                true
+            }
+
+            /// A node can be reached by several labels, hence edges are always checked
+            fn is_discovered(&self, _node: NodeType<W>) -> bool { false }
+
+            /// proceed only if the edge results to a lower cost label for `dst` that doesn't violate the constraints
+            fn pre_process_edge(&mut self, src: Node, dst: NodeType<W>) -> bool {
+                let label = (dst.into(), self.current.1 + 1);
+                let cost = self.labels[&self.current].0 + dst.cost();
+                if !self.constraints.allows(src, dst, label.1, cost)
+                    || self.settled.get(&label.0).is_some_and(|&settled| settled <= label.1)
+                    || self.labels.get(&label).is_some_and(|&(found, _)| found <= cost) {
+                    return false
+                }
+                self.labels.insert(label, (cost, Some(src)));
+                true
+            }
 
+            /// Construct the label of the node reached through the label being processed
+            fn node_to_queued(&self, node: Node) -> Self::QueueItem { (node, self.current.1 + 1) }
+
+            /// Push the label into the queue, or lower its cost if it's already queued
+            fn push(&mut self, item: Self::QueueItem) {
+                let cost = self.labels[&item].0;
+                if !self.queue.decrease_key(&item, cost) && !self.queue.contains(&item) {
+                    self.queue.push(item, cost);
+                }
+            }
+
+            /// Extract the path of the label being processed, along with its cost
+            fn extract_path(&self, _goal: Node) -> Self::Output {
+                (extract_label(&self.labels, self.current), self.labels[&self.current].0)
+            }
+        }
+
+        if constraints.excluded.contains(&start) { return None }
+        CSState {
+            labels: HashMap::new(),
+            settled: HashMap::new(),
+            queue: IndexedHeap::new(),
+            current: (start, 0),
+            constraints
+        }.path_search(self, start, goal)
     }
-// ANCHOR_END: graphs_search_path_min_cost
+    // ANCHOR_END: graphs_search_path_constrained
 }
 
 #[cfg(test)]
 mod test {
-    use crate::graphs::{Graph, Node};
+    use std::{fs::File, io::BufReader};
+    use crate::graphs::{Graph, Node, generate, weight::OrderedFloat};
     use super::*;
 
+    fn load(filename: &str, node_pat: char, edge_pat: char) -> Graph {
+        Graph::import_text_graph(BufReader::new(File::open(filename).expect("cannot open file")), node_pat, edge_pat, GraphKind::Directed)
+            .expect("graph couldn't be loaded")
+    }
+
     #[test]
     fn  test_path_search_small_graph() {
         // ( input graph, starting node, array with expected distances)
@@ -276,7 +721,7 @@ mod test {
                 ]
             );
         let (inp, start, out) = test_data;
-        let g = Graph::import_edges(&inp).expect("couldn't load edges");
+        let g = Graph::import_edges(&inp, GraphKind::Directed).expect("couldn't load edges");
 
         out.into_iter()
             .for_each(|(goal, exp)|{
@@ -299,7 +744,7 @@ mod test {
             (188, 2),
             (197, 2)
         ];
-        let g = Graph::import_text_graph("src/graphs/txt/ps_input_random_10_16.txt", '\t',',').expect("graph couldn't be loaded");
+        let g = load("src/graphs/txt/ps_input_random_10_16.txt", '\t', ',');
 
         data.into_iter()
             .for_each(|(goal, dist)| {
@@ -320,7 +765,7 @@ mod test {
             (6, 3)
         ];
         let edge_list = include!("small_graph.in");
-        let g = Graph::from_edge_list(&edge_list);
+        let g = Graph::from_edge_list(&edge_list, GraphKind::Directed);
 
         data.into_iter()
             .for_each(|(goal, cost)| {
@@ -344,7 +789,7 @@ mod test {
             (188, 830),
             (197, 839)
         ];
-        let g = Graph::import_text_graph("src/graphs/txt/ps_input_random_10_16.txt",'\t',',').expect("graph couldn't be loaded");
+        let g = load("src/graphs/txt/ps_input_random_10_16.txt", '\t', ',');
 
         data.into_iter()
             .for_each(|(goal, cost)| {
@@ -354,4 +799,110 @@ mod test {
                 assert_eq!(path.unwrap().1, cost);
             })
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_path_astar() {
+        let g = load("src/graphs/txt/ps_input_random_10_16.txt", '\t', ',');
+        // a zero heuristic turns A* into Dijkstra
+        [7, 37, 59, 82, 99, 115, 133, 165, 188, 197].into_iter()
+            .for_each(|goal| {
+                assert_eq!(g.path_astar(1, goal, |_| 0).map(|(_, c)| c), g.path_shortest(1, goal).map(|(_, c)| c));
+            });
+
+        // on a grid, the manhattan distance never overestimates the remaining cost
+        let (rows, cols) = (20, 30);
+        let g = generate::with_random_costs(&generate::grid(rows, cols, GraphKind::Undirected), 1..10, 1);
+        let goal = rows * cols;
+        let manhattan = |node: Node| {
+            let (r, c) = ((node - 1) / cols, (node - 1) % cols);
+            ((rows - 1 - r) + (cols - 1 - c)) as Cost
+        };
+        let (path, cost) = g.path_astar(1, goal, manhattan).expect("grid is connected");
+        assert_eq!(Some(cost), g.path_shortest(1, goal).map(|(_, c)| c));
+        assert_eq!((path.first(), path.last()), (Some(&1), Some(&goal)));
+        assert_eq!(g.path_astar(1, goal + 1, manhattan), None);
+
+        // the heuristic never overestimates, yet it isn't consistent as h(2) = 4 > cost(2 -> 4) + h(4) = 1,
+        // so node 4 is first expanded through the costlier 1 -> 3 -> 4 and must be reopened once 2 is expanded
+        let g = Graph::from_edge_list(&[(1, 2, 1), (2, 4, 1), (1, 3, 1), (3, 4, 2), (4, 5, 3)], GraphKind::Directed);
+        let inconsistent = |node: Node| if node == 2 { 4 } else { 0 };
+        assert_eq!(g.path_astar(1, 5, inconsistent), Some((vec![1, 2, 4, 5], 5)));
+    }
+    #[test]
+    fn test_path_constrained() {
+        // 3 x 3 grid, where node `(row, col)` is numbered `row * 3 + col + 1`
+        let g: Graph = generate::grid(3, 3, GraphKind::Undirected);
+        let g = generate::with_random_costs(&g, 1..2, 1);
+
+        // no constraints is the same as the plain searches
+        let none = PathConstraints::new();
+        assert_eq!(g.path_distance_constrained(1, 9, &none).map(|(_, d)| d), g.path_distance(1, 9).map(|(_, d)| d));
+        assert_eq!(g.path_shortest_constrained(1, 9, &none).map(|(_, c)| c), g.path_shortest(1, 9).map(|(_, c)| c));
+
+        // the middle column is out of service, except its bottom node
+        let detour = PathConstraints::new().exclude([2, 5]);
+        for (path, cost) in [g.path_distance_constrained(1, 3, &detour), g.path_shortest_constrained(1, 3, &detour)].into_iter().flatten() {
+            assert_eq!((path, cost), (vec![1, 4, 7, 8, 9, 6, 3], 6));
+        }
+        assert_eq!(g.path_shortest_constrained(1, 3, &PathConstraints::new().exclude([2, 5, 8])), None);
+        assert_eq!(g.path_shortest_constrained(2, 3, &detour), None);
+
+        // edges leaving the first row downwards are over capacity
+        let filter = PathConstraints::new().edge_filter(|src, dst| !(src <= 3 && Node::from(dst) > 3));
+        assert_eq!(g.path_shortest_constrained(1, 9, &filter), None);
+        assert_eq!(g.path_shortest_constrained(9, 1, &filter).map(|(_, c)| c), Some(4));
+
+        // the detour needs 6 hops at a cost of 6
+        assert_eq!(g.path_distance_constrained(1, 3, &PathConstraints::new().exclude([2, 5]).max_hops(5)), None);
+        assert_eq!(g.path_shortest_constrained(1, 3, &PathConstraints::new().exclude([2, 5]).max_cost(5)), None);
+        assert!(g.path_shortest_constrained(1, 3, &PathConstraints::new().exclude([2, 5]).max_cost(6)).is_some());
+        assert!(g.path_distance_constrained(1, 3, &PathConstraints::new().exclude([2, 5]).max_hops(6)).is_some());
+
+        // the minimum cost path 1 -> 2 -> 3 -> 4 needs 3 hops, while the direct edge costs more
+        let g = Graph::from_edge_list(&[(1, 2, 1), (2, 3, 1), (3, 4, 1), (1, 4, 5), (2, 4, 3)], GraphKind::Directed);
+        assert_eq!(g.path_shortest_constrained(1, 4, &PathConstraints::new()), Some((vec![1, 2, 3, 4], 3)));
+        assert_eq!(g.path_shortest_constrained(1, 4, &PathConstraints::new().max_hops(2)), Some((vec![1, 2, 4], 4)));
+        assert_eq!(g.path_shortest_constrained(1, 4, &PathConstraints::new().max_hops(1)), Some((vec![1, 4], 5)));
+        assert_eq!(g.path_shortest_constrained(1, 4, &PathConstraints::new().max_hops(1).max_cost(4)), None);
+        // likewise, the fewest edges path costs more than a longer one
+        assert_eq!(g.path_distance_constrained(1, 4, &PathConstraints::new()), Some((vec![1, 4], 1)));
+        assert_eq!(g.path_distance_constrained(1, 4, &PathConstraints::new().max_cost(4)), Some((vec![1, 2, 4], 2)));
+        assert_eq!(g.path_distance_constrained(1, 4, &PathConstraints::new().max_cost(3)), Some((vec![1, 2, 3, 4], 3)));
+        assert_eq!(g.path_distance_constrained(1, 4, &PathConstraints::new().max_cost(3).max_hops(2)), None);
+    }
+    #[test]
+    fn test_path_bidirectional() {
+        let g = load("src/graphs/txt/ps_input_random_10_16.txt", '\t', ',');
+        for (start, goal) in [(1, 7), (1, 37), (1, 197), (59, 82), (200, 1), (5, 5)] {
+            let path = g.path_bidirectional(start, goal);
+            assert_eq!(path.as_ref().map(|(_, c)| *c), g.path_shortest(start, goal).map(|(_, c)| c));
+            // the path must lead from start to goal through existing edges and add up to the cost
+            let (path, cost) = path.expect("path exists");
+            assert_eq!((path.first(), path.last()), (Some(&start), Some(&goal)));
+            let total = path.windows(2)
+                .map(|e| g.edges[&e[0]].iter().filter_map(|&nt| match nt { NC(n, c) if n == e[1] => Some(c), _ => None }).min().expect("edge exists"))
+                .sum::<Cost>();
+            assert_eq!(total, cost);
+        }
+
+        let g = Graph::from_edge_list(&[(1, 2, 1), (3, 2, 1)], GraphKind::Directed);
+        assert_eq!(g.path_bidirectional(1, 3), None);
+        assert_eq!(g.path_bidirectional(1, 2), Some((vec![1, 2], 1)));
+
+        // unweighted edges cost one
+        let g: Graph = generate::grid(3, 4, GraphKind::Undirected);
+        assert_eq!(g.path_bidirectional(1, 12).map(|(_, c)| c), Some(5));
+    }
+    #[test]
+    fn test_path_shortest_weight_types() {
+        let edge_list: Vec<(Node, Node, Cost)> = include!("small_graph.in");
+
+        let edges = edge_list.iter().map(|&(s, d, c)| (s, d, c as u64 * 10_000_000_000)).collect::<Vec<_>>();
+        let g = Graph::from_edge_list(&edges, GraphKind::Directed);
+        assert_eq!(g.path_shortest(2, 5), Some((vec![2, 1, 3, 5], 40_000_000_000)));
+
+        let edges = edge_list.iter().map(|&(s, d, c)| (s, d, OrderedFloat(c as f64 / 4.0))).collect::<Vec<_>>();
+        let g = Graph::from_edge_list(&edges, GraphKind::Directed);
+        assert_eq!(g.path_shortest(2, 5), Some((vec![2, 1, 3, 5], OrderedFloat(1.0))));
+        assert_eq!(g.path_distance(2, 5), Some((vec![2, 5], OrderedFloat(1.0))));
+    }
+}
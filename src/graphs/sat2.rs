@@ -0,0 +1,163 @@
+//! 2-SAT solver, that is, satisfiability of a conjunction of clauses with two literals each, e.g. `(x1 ∨ ¬x2) ∧ (x2 ∨ x3)`
+//! Every clause `(a ∨ b)` is turned into the implications `¬a -> b` and `¬b -> a`, hence the formula is
+//! unsatisfiable exactly when a variable and its negation fall within the same strongly connected component
+use super::{*, path_search::PathSearch, scc::ConnectedComponents};
+
+/// A variable `v`, numbered from `1`, as `v` or its negation as `-v`, same as the DIMACS format
+pub type Literal = isize;
+
+/// 2-SAT formula built up one clause at a time, backed by its implication graph
+/// ```
+/// use csx3::graphs::sat2::Sat2;
+///
+/// // (x1 ∨ x2) ∧ (¬x1 ∨ x2) ∧ (¬x2 ∨ x3)
+/// let mut sat = Sat2::new();
+/// sat.add_clause(1, 2);
+/// sat.add_clause(-1, 2);
+/// sat.add_clause(-2, 3);
+/// let assignment = sat.solve().expect("formula is satisfiable");
+/// assert!(assignment[2] && assignment[3]);
+///
+/// // x1 ∧ ¬x1, that is, x1 -> ¬x1 -> x1
+/// sat.add_clause(1, 1);
+/// sat.add_clause(-1, -1);
+/// assert_eq!(sat.solve(), Err(vec![1, -1, 1]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sat2 {
+    implications: Graph,
+    /// highest variable found in the clauses
+    vars: usize
+}
+
+impl Sat2 {
+    /// Construct an empty formula, which is trivially satisfiable
+    pub fn new() -> Sat2 {
+        Sat2 { implications: Graph::new(GraphKind::Directed), vars: 0 }
+    }
+    /// Number of variables, that is, the highest variable found in the clauses
+    pub fn vars(&self) -> usize {
+        self.vars
+    }
+    /// The implication graph, where variable `v` is node `2v` and its negation `-v` is node `2v + 1`
+    pub fn implications(&self) -> &Graph {
+        &self.implications
+    }
+    /// Adds the clause `(a ∨ b)`; use `add_clause(a, a)` to force `a` to be true
+    /// Panics if either literal is `0`, given variables are numbered from `1`
+    pub fn add_clause(&mut self, a: Literal, b: Literal) {
+        for lit in [a, b] {
+            assert_ne!(lit, 0, "add_clause(): variables are numbered from 1");
+            let var = lit.unsigned_abs();
+            self.vars = self.vars.max(var);
+            self.implications.nodes.extend([Self::node(var as Literal), Self::node(-(var as Literal))]);
+        }
+        // either `a` holds, or `b` must hold, and vice versa
+        self.implications.insert_edge(Self::node(-a), NodeType::N(Self::node(b)));
+        self.implications.insert_edge(Self::node(-b), NodeType::N(Self::node(a)));
+    }
+    /// Returns an assignment satisfying all clauses, where `assignment[v]` holds the value of variable `v`
+    /// and `assignment[0]` is unused, or the chain of implications `v -> ... -> -v -> ... -> v` of a variable
+    /// that implies its own negation and vice versa, hence it can be neither true nor false
+    /// Variables not found in any clause are set to `false`
+    pub fn solve(&self) -> Result<Vec<bool>, Vec<Literal>> {
+        // Tarjan's components come in reverse topological order of the implication graph
+        let components = self.implications.strongly_connected_tarjan()
+            .expect("implication graph is directed");
+        let component = components.iter()
+            .enumerate()
+            .flat_map(|(id, nodes)| nodes.iter().map(move |&node| (node, id)))
+            .collect::<HashMap<_, _>>();
+
+        let mut assignment = vec![false; self.vars + 1];
+        for var in 1..=self.vars as Literal {
+            let (Some(pos), Some(neg)) = (component.get(&Self::node(var)), component.get(&Self::node(-var))) else { continue };
+            if pos == neg {
+                return Err(self.conflict(var))
+            }
+            // the literal coming later in topological order cannot imply its negation, hence it is safe to set it true
+            assignment[var as usize] = pos < neg;
+        }
+        Ok(assignment)
+    }
+    /// Shortest chain of implications `var -> ... -> -var` followed by `-var -> ... -> var`,
+    /// given both literals fall within the same strongly connected component
+    fn conflict(&self, var: Literal) -> Vec<Literal> {
+        let (pos, neg) = (Self::node(var), Self::node(-var));
+        let (mut chain, _) = self.implications.path_distance(pos, neg).expect("literals are strongly connected");
+        let (back, _) = self.implications.path_distance(neg, pos).expect("literals are strongly connected");
+        chain.extend(back.into_iter().skip(1));
+        chain.into_iter().map(Self::literal).collect()
+    }
+    /// Node of the implication graph representing the literal
+    fn node(lit: Literal) -> Node {
+        2 * lit.unsigned_abs() + usize::from(lit < 0)
+    }
+    /// Literal represented by the node of the implication graph
+    fn literal(node: Node) -> Literal {
+        let var = (node / 2) as Literal;
+        if node % 2 == 1 { -var } else { var }
+    }
+}
+
+impl Default for Sat2 {
+    fn default() -> Self { Sat2::new() }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+    use super::*;
+
+    /// Does the assignment satisfy all clauses ?
+    fn satisfies(clauses: &[(Literal, Literal)], assignment: &[bool]) -> bool {
+        let value = |lit: Literal| assignment[lit.unsigned_abs()] == (lit > 0);
+        clauses.iter().all(|&(a, b)| value(a) || value(b))
+    }
+    /// Is the conflict a chain of implications `v -> ... -> -v -> ... -> v` ?
+    fn is_conflict(sat: &Sat2, chain: &[Literal]) -> bool {
+        let implies = |a: Literal, b: Literal| sat.implications().edges.get(&Sat2::node(a))
+            .is_some_and(|edges| edges.contains(&NodeType::N(Sat2::node(b))));
+        let (Some(&first), Some(&last)) = (chain.first(), chain.last()) else { return false };
+        first == last && chain.contains(&-first) && chain.windows(2).all(|w| implies(w[0], w[1]))
+    }
+
+    #[test]
+    fn test_sat2() {
+        let mut sat = Sat2::new();
+        assert_eq!(sat.solve(), Ok(vec![false]));
+
+        // x1 -> x2 -> x3 -> ¬x1, hence x1 must be false
+        let clauses = [(-1, 2), (-2, 3), (-3, -1), (1, 4), (-4, 5)];
+        clauses.iter().for_each(|&(a, b)| sat.add_clause(a, b));
+        assert_eq!(sat.vars(), 5);
+        let assignment = sat.solve().expect("formula is satisfiable");
+        assert!(satisfies(&clauses, &assignment));
+        assert!(!assignment[1] && assignment[4] && assignment[5]);
+
+        // forcing x1 to be true closes the loop x1 -> ... -> ¬x1 -> x1
+        sat.add_clause(1, 1);
+        let conflict = sat.solve().expect_err("formula is unsatisfiable");
+        assert!(is_conflict(&sat, &conflict), "{conflict:?}");
+        assert_eq!((conflict.len(), conflict.first(), conflict[3], conflict.last()), (5, Some(&1), -1, Some(&1)));
+    }
+    #[test]
+    fn test_sat2_brute_force() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..200 {
+            let vars = rng.gen_range(1..=6);
+            let mut literal = || rng.gen_range(1..=vars as Literal) * if rng.gen_bool(0.5) { 1 } else { -1 };
+            let clauses = (0..8).map(|_| (literal(), literal())).collect::<Vec<_>>();
+
+            let mut sat = Sat2::new();
+            clauses.iter().for_each(|&(a, b)| sat.add_clause(a, b));
+            // try out every possible assignment
+            let satisfiable = (0..1 << sat.vars())
+                .any(|bits: usize| satisfies(&clauses, &(0..=sat.vars()).map(|v| v > 0 && bits & (1 << (v - 1)) != 0).collect::<Vec<_>>()));
+            match sat.solve() {
+                Ok(assignment) => assert!(satisfiable && satisfies(&clauses, &assignment), "{clauses:?}"),
+                Err(conflict) => assert!(!satisfiable && is_conflict(&sat, &conflict), "{clauses:?}")
+            }
+        }
+    }
+}
@@ -1,4 +1,3 @@
-use std::collections::{BinaryHeap};
 use super::{*, NodeState::{ Discovered, Processed, Undiscovered }};
 
 // ANCHOR: graphs_abstract_dfs
@@ -7,10 +6,39 @@ use super::{*, NodeState::{ Discovered, Processed, Undiscovered }};
 /// - Node pre-processing step fn()
 /// - Node post-processing step fn()
 /// - Node pre-processing edge fn()
-/// - abort recursion fn()
+/// - abort search fn()
 /// - Path return fn()
 /// - node state fn()
-trait DFSearch {
+///
+/// Implement it on your own state structure to write custom traversals, for example, collecting the nodes reachable from a start node
+/// ```
+/// use csx3::graphs::{*, scc::DFSearch};
+///
+/// struct Reach { tracker: Tracker<usize>, path: Vec<Node> }
+///
+/// impl DFSearch<Cost> for Reach {
+///     type Output = Vec<Node>;
+///
+///     fn pre_process_node(&mut self, node: Node) -> &mut Self {
+///         self.tracker[node].visited(NodeState::Discovered);
+///         self
+///     }
+///     fn post_process_node(&mut self, node: Node) -> &mut Self {
+///         self.tracker[node].visited(NodeState::Processed);
+///         self.path.push(node);
+///         self
+///     }
+///     fn path(&self) -> &Vec<Node> { &self.path }
+///     fn is_discovered(&self, node: Node) -> bool { self.tracker[node].is_discovered() }
+/// }
+///
+/// let g: Graph = Graph::import_edges(&[vec![1, 2, 3], vec![2, 4], vec![5, 1]], GraphKind::Directed).unwrap();
+/// let mut reach = Reach { tracker: g.get_tracker(NodeState::Undiscovered, 0, None), path: Vec::new() };
+/// let mut nodes = reach.path_search(&g, 1).unwrap().clone();
+/// nodes.sort();
+/// assert_eq!(nodes, vec![1, 2, 3, 4]);
+/// ```
+pub trait DFSearch<W: Weight> {
     type Output;
 
     /// work to be done before edges are explored, that is, discovered but not processed
@@ -24,9 +52,9 @@ trait DFSearch {
     /// work to be done after the node pre-processed and before the edges is explored
     /// uses incl. check for loops, categorize edge into types, etc
     /// default implementation does nothing otherwise you have to override
-    fn pre_process_edge(&mut self, _edge: Edge) -> &mut Self { self }
+    fn pre_process_edge(&mut self, _edge: Edge<W>) -> &mut Self { self }
 
-    /// Abort the recursion
+    /// Abort the search
     /// uses incl. detecting the graph is not Direct acyclic, etc
     fn abort(&self) -> bool { false }
 
@@ -37,64 +65,68 @@ trait DFSearch {
     fn is_discovered(&self, node: Node) -> bool;
 
     /// Default implementation of depth first search
-    fn path_search(&mut self, g: &Graph, start: Node) -> Option<&Self::Output> {
+    /// An explicit stack replaces recursion so that long paths cannot overflow the thread's stack,
+    /// while nodes are entered & exited, and edges are explored, in the same order as a recursive search would
+    fn path_search(&mut self, g: &Graph<W>, start: Node) -> Option<&Self::Output> {
         // Entering the node at time tick()
         if self.pre_process_node(start).abort() { return None }
 
-        // processing the edges
-        // println!("Enter: {start}:{:?}", self.tracker[start]);
-        if let Some(edges) = g.edges.get(&start) {
-            for &dst in edges {
-                let d = dst;
-
-                if self.pre_process_edge(Edge(start,d)).abort() { return None };
-
-                if !self.is_discovered(d.into()) {
-                    self.path_search(g, d.into());
+        // nodes under exploration along with their edges yet to be explored
+        let mut stack = vec![(start, g.edges.get(&start).map(|edges| edges.iter()))];
+        while let Some((src, edges)) = stack.last_mut() {
+            let src = *src;
+            // processing the next edge, if any
+            if let Some(&dst) = edges.as_mut().and_then(|edges| edges.next()) {
+                if self.pre_process_edge(Edge(src, dst)).abort() { return None };
+
+                if !self.is_discovered(dst.into()) {
+                    // Entering the node at time tick()
+                    if self.pre_process_node(dst.into()).abort() { return None }
+                    stack.push((dst.into(), g.edges.get(&dst.into()).map(|edges| edges.iter())));
                 }
+            } else {
+                // all edges explored; Exiting the node at time tick()
+                stack.pop();
+                if self.post_process_node(src).abort() { return None };
             }
         }
-        // Exiting the node at time tick()
-        if self.post_process_node(start).abort() { return None };
-        // println!("Exit: {start}:{:?}", self.tracker[start]);
         Some(self.path())
     }
 }
 // ANCHOR_END: graphs_abstract_dfs
 // ANCHOR: graphs_scc_state
 /// GraphState struct enable us to maintain the processing state of the graph
-/// and while we apply a recursive approach in searching the graph
+/// across the entry & exit steps of the stack-driven depth first search
 struct GraphState {
-    tracker: Tracker,
-    queue: BinaryHeap<NodeType>,
-    time: Cost,
+    tracker: Tracker<usize>,
+    /// nodes along with their exit time, in the order they exit
+    finished: Vec<(Node, usize)>,
+    time: usize,
     path: Vec<Node>
 }
 
 impl GraphState {
     /// Construct a new `GraphState` given a `Graph`
-    fn new(g: &Graph) -> GraphState {
+    fn new<W>(g: &Graph<W>) -> GraphState {
         GraphState {
             tracker: g.get_tracker(Undiscovered, 0, None),
-            queue: BinaryHeap::new(),
+            finished: Vec::new(),
             time: 0,
             path: Vec::new()
         }
     }
 
-    /// Extract from `BinaryHeap` the exit times per ordered from max -> min
-    fn get_timings(&self) -> Vec<(Node, Cost)> {
-        self.queue.iter().rev().map(|&s| {
-            let NC(n, c) = s else { panic!("get_timings(): node type is not NodeType::NC") };
-            (n,c)
-        }  ).collect::<Vec<_>>()
+    /// Extract the exit times per node ordered from max -> min
+    fn get_timings(&self) -> Vec<(Node, usize)> {
+        // nodes are pushed as they exit, hence exit times are already in increasing order
+        self.finished.iter().rev().copied().collect::<Vec<_>>()
     }
 }
 
 /// Graph State implements DFSearch trait and particularly provides specific implementation for
 /// the calculation of the strongly connected components, in terms of node post/pre processing fn(),
 /// path return fn() and node state fn()
-impl DFSearch for GraphState {
+impl<W: Weight> DFSearch<W> for GraphState {
     type Output = Vec<Node>;
 
     /// capture time of entry and set node state to visited,
@@ -112,7 +144,7 @@ impl DFSearch for GraphState {
         // Exiting the node at time tick()
         self.time += 1;
         self.tracker[node].visited(Processed).distance(self.time);
-        self.queue.push(NC(node, self.time));
+        self.finished.push((node, self.time));
         self.path.push(node);
         self
     }
@@ -131,64 +163,196 @@ impl DFSearch for GraphState {
 
 // ANCHOR: graphs_scc
 pub trait ConnectedComponents {
-    fn strongly_connected(&self) -> Vec<Vec<Node>>;
+    /// Strongly connected components using Kosaraju's algorithm, that is, two depth first passes,
+    /// the second one over the transposed graph
+    fn strongly_connected(&self) -> Result<Vec<Vec<Node>>, GraphError>;
+    /// Strongly connected components using Tarjan's algorithm, that is, a single depth first pass with no transposed graph
+    /// Components are returned in reverse topological order, that is, a component has no edges to any component after it
+    fn strongly_connected_tarjan(&self) -> Result<Vec<Vec<Node>>, GraphError>;
 }
 
-impl ConnectedComponents for Graph {
-    fn strongly_connected(&self) -> Vec<Vec<Node>> {
+impl<W: Weight> ConnectedComponents for Graph<W> {
+    fn strongly_connected(&self) -> Result<Vec<Vec<Node>>, GraphError> {
+
+        // strong connectivity is only meaningful when edges are one-way
+        self.ensure_kind(GraphKind::Directed)?;
 
         // initiate the run state structure for calculating the scc of the graph
-        // and in order to enable recursive searching in rust
+        // that is kept across the entry & exit steps of the depth first search
         let mut gs = GraphState::new(self);
 
         // Pass 1: Find all paths and calculate entry and exit times per node
         self.nodes.iter()
             .for_each(|&start| {
-                // println!("Start >> {start}");
                 if !gs.tracker[start].is_discovered() {
-                    let path = gs.path_search(self, start);
-                    println!("Pass 1: Path {:?}",path);
+                    gs.path_search(self, start);
                     gs.path.clear();
                 }
             });
 
         // Extract node sequence ordered by highest exit times
         let v = gs.get_timings();
-        println!("Timings: {:?}",v);
         // reverse the graph edges
         let tg = self.transpose();
         // reset run state
         gs = GraphState::new( &tg);
 
         // Pass 2: Identify and store each strongly connected component identified
-        v.into_iter()
+        Ok(v.into_iter()
             .fold(Vec::new(),|mut components, (node, _)| {
-                if !gs.is_discovered(node) {
+                if !gs.tracker[node].is_discovered() {
                     // extract new component
                     let component = gs.path_search(&tg, node ).unwrap();
-                    println!("Pass 2: Component [{}]{:?}", component.len(), component);
                     // store component found
                     components.push(component.clone() );
                     // reset path so to remove last found component
                     gs.path.clear();
                 }
                 components
-            })
+            }))
+    }
+    // ANCHOR: graphs_scc_tarjan
+    fn strongly_connected_tarjan(&self) -> Result<Vec<Vec<Node>>, GraphError> {
+
+        // strong connectivity is only meaningful when edges are one-way
+        self.ensure_kind(GraphKind::Directed)?;
+
+        let mut ts = TarjanState::new(self);
+        for &start in &self.nodes {
+            if !ts.tracker[start].is_discovered() {
+                ts.path_search(self, start);
+            }
+        }
+        Ok(ts.components)
     }
+    // ANCHOR_END: graphs_scc_tarjan
 }
 // ANCHOR_END: graphs_scc
+// ANCHOR: graphs_scc_tarjan_state
+/// Tarjan's algorithm state, where every node gets an entry index along with a low-link value, that is,
+/// the lowest index reachable from the node's subtree via a node still on the stack
+/// Nodes remain `Discovered` while on the stack and become `Processed` once assigned to a component
+struct TarjanState {
+    tracker: Tracker<usize>,
+    low: HashMap<Node, usize>,
+    stack: Vec<Node>,
+    time: usize,
+    components: Vec<Vec<Node>>
+}
+
+impl TarjanState {
+    /// Construct a new `TarjanState` given a `Graph`
+    fn new<W>(g: &Graph<W>) -> TarjanState {
+        TarjanState {
+            tracker: g.get_tracker(Undiscovered, 0, None),
+            low: HashMap::new(),
+            stack: Vec::new(),
+            time: 0,
+            components: Vec::new()
+        }
+    }
+}
+
+impl<W: Weight> DFSearch<W> for TarjanState {
+    type Output = Vec<Vec<Node>>;
+
+    /// assign the entry index, which is also the initial low-link, and push the node on the stack
+    fn pre_process_node(&mut self, node: Node) -> &mut Self {
+        self.time += 1;
+        self.tracker[node].visited(Discovered).distance(self.time);
+        self.low.insert(node, self.time);
+        self.stack.push(node);
+        self
+    }
+
+    /// pass the low-link on to the parent and, if the node is the root of a component,
+    /// pop the component off the stack
+    fn post_process_node(&mut self, node: Node) -> &mut Self {
+        let low = self.low[&node];
+        if let Some(parent) = self.tracker[node].parent {
+            self.low.entry(parent).and_modify(|l| *l = low.min(*l));
+        }
+        if low == self.tracker[node].dist {
+            let pos = self.stack.iter().rposition(|&n| n == node).expect("node is on the stack");
+            let component = self.stack.split_off(pos);
+            component.iter().for_each(|&n| { self.tracker[n].visited(Processed); });
+            self.components.push(component);
+        }
+        self
+    }
+
+    /// an undiscovered `dst` becomes the child of `src`, while a `dst` still on the stack lowers the low-link of `src`
+    fn pre_process_edge(&mut self, edge: Edge<W>) -> &mut Self {
+        let Edge(src, dst) = edge;
+        let dst = Node::from(dst);
+        match self.tracker[dst].visited {
+            Undiscovered => { self.tracker[dst].parent(src); },
+            Discovered => {
+                let index = self.tracker[dst].dist;
+                self.low.entry(src).and_modify(|l| *l = index.min(*l));
+            },
+            Processed => {}
+        }
+        self
+    }
+
+    /// return the components found so far
+    fn path(&self) -> &Self::Output {
+        &self.components
+    }
+
+    /// return whether the node has been visited
+    fn is_discovered(&self, node: Node) -> bool {
+        self.tracker[node].is_discovered()
+    }
+}
+// ANCHOR_END: graphs_scc_tarjan_state
+// ANCHOR: graphs_scc_condensation
+impl<W: Weight> Graph<W> {
+    /// Condensation of a directed graph, that is, the acyclic graph resulting from collapsing
+    /// each strongly connected component into a single node
+    /// Returns the condensation along with the component id of every node, where
+    /// - component ids are numbered `0..k` in topological order, hence edges only lead to higher ids
+    /// - edges between two components keep their cost, with any duplicates merged
+    pub fn condensation(&self) -> Result<(Graph<W>, HashMap<Node, Node>), GraphError> {
+        let components = self.strongly_connected_tarjan()?;
+        let k = components.len();
+
+        // Tarjan's components are in reverse topological order
+        let component = components.iter().rev()
+            .enumerate()
+            .flat_map(|(id, nodes)| nodes.iter().map(move |&node| (node, id)))
+            .collect::<HashMap<_, _>>();
+
+        let mut dag = Graph { nodes: (0..k).collect(), ..Graph::new(GraphKind::Directed) };
+        for (&src, edges) in &self.edges {
+            for &dst in edges {
+                let (src, dst_id) = (component[&src], component[&dst.into()]);
+                if src != dst_id {
+                    dag.insert_edge(src, match dst { NodeType::N(_) => NodeType::N(dst_id), NC(_, cost) => NC(dst_id, cost) });
+                }
+            }
+        }
+        Ok((dag, component))
+    }
+}
+// ANCHOR_END: graphs_scc_condensation
 // ANCHOR: graphs_scc_traversal
-impl Graph {
-    pub fn transpose(&self) -> Graph {
+impl<W: Weight> Graph<W> {
+    pub fn transpose(&self) -> Graph<W> {
+        // an undirected graph is its own transpose
+        if self.kind == GraphKind::Undirected {
+            return self.clone()
+        }
         self.nodes.iter()
-            .fold(Graph::new(), |mut g, &node| {
+            .fold(Graph::new(self.kind), |mut g, &node| {
                 g.nodes.insert(node);
-                // reverse the edges for this node, if any
+                // reverse the edges for this node, if any, keeping their costs
                 if let Some(edges) = self.edges.get(&node) {
                     edges.iter()
                         .for_each(|&e|{
                             g.nodes.insert(e.into());
-                            g.edges.entry(e.into()).or_default().insert(node.into());
+                            g.edges.entry(e.into()).or_default().insert(e.reverse(node));
                         });
                 }
                 g
@@ -201,14 +365,14 @@ impl Graph {
 /// Graph state that we need to maintain
 /// for the topological sort algorithm
 struct TState {
-    tracker: Tracker,
+    tracker: Tracker<usize>,
     path: Vec<Node>,
     abort: bool
 }
 
 impl TState {
     /// Construct a new `GraphState` given a `Graph`
-    fn new(g: &Graph) -> TState {
+    fn new<W>(g: &Graph<W>) -> TState {
         TState {
             tracker: g.get_tracker(Undiscovered, 0, None),
             path: Vec::new(),
@@ -220,7 +384,7 @@ impl TState {
 /// Topological sort implementation of the TState
 /// There is no need for exit/entry time or tracking parent node.
 /// Here we only need to save the `node` in the `tracker.path` following its full processing
-impl DFSearch for TState {
+impl<W: Weight> DFSearch<W> for TState {
     type Output = Vec<Node>;
 
     /// mark node as visited but not processed
@@ -239,7 +403,7 @@ impl DFSearch for TState {
     /// before we jump into the edge for further exploration
     /// we check if the edge is actually a node already `Discovered` but not `Processed`
     /// if that is the case, we set the abort flag to `True`
-    fn pre_process_edge(&mut self, edge: Edge) -> &mut Self {
+    fn pre_process_edge(&mut self, edge: Edge<W>) -> &mut Self {
         let Edge(_,dst) = edge;
         if self.tracker[dst.into()].visited == Discovered {
             self.abort = true;
@@ -247,7 +411,7 @@ impl DFSearch for TState {
         self
     }
 
-    /// Implement the abort fn() so we can stop the path search recursion
+    /// Implement the abort fn() so we can stop the path search early
     fn abort(&self) -> bool {
         self.abort
     }
@@ -266,12 +430,19 @@ impl DFSearch for TState {
 // ANCHOR: graphs_topological_sort
 /// Topological Sort trait
 pub trait TopologicalSort {
-    fn topological_sort(&self) -> Option<Vec<Node>>;
+    fn topological_sort(&self) -> Result<Option<Vec<Node>>, GraphError>;
+    /// Same as `topological_sort()` however a graph containing a cycle results to
+    /// `GraphError::Cycle` holding one of the offending cycles, rather than `None`
+    fn topological_order(&self) -> Result<Vec<Node>, GraphError>;
 }
 /// Graph implementation of Topological Sort
-impl TopologicalSort for Graph {
+impl<W: Weight> TopologicalSort for Graph<W> {
     /// Implementation of topological sort for Graph
-    fn topological_sort(&self) -> Option<Vec<Node>> {
+    /// Returns `None` when the graph contains a cycle
+    fn topological_sort(&self) -> Result<Option<Vec<Node>>, GraphError> {
+        // every undirected edge forms a cycle hence only directed graphs can be sorted
+        self.ensure_kind(GraphKind::Directed)?;
+
         // initiate the run state structure for calculating the topological sort of the graph
         let mut ts = TState::new(self);
 
@@ -280,22 +451,122 @@ impl TopologicalSort for Graph {
         // see post_processing() of TState implementation of DFSearch
         for &node in &self.nodes {
             // if node is not yet visited && search hasn't thrown a NONE, that is, we've found a circle
-            if !ts.is_discovered(node)
+            if !ts.tracker[node].is_discovered()
                 && ts.path_search(self, node).is_none() {
-                return None
+                return Ok(None)
             }
         }
 
         // Extract & reverse path from tracker so we extract the topological sort
         ts.path.reverse();
-        Some(ts.path)
+        Ok(Some(ts.path))
+    }
+    fn topological_order(&self) -> Result<Vec<Node>, GraphError> {
+        match self.topological_sort()? {
+            Some(order) => Ok(order),
+            None => Err(GraphError::Cycle { cycle: self.find_cycle().expect("topological sort found a cycle") })
+        }
     }
 }
 // ANCHOR_END: graphs_topological_sort
+// ANCHOR: graphs_find_cycle_state
+/// Cycle detection state; a node remains `Discovered` while its edges are being explored, hence an edge
+/// leading to a `Discovered` node is a back edge closing a cycle along the nodes' parents
+struct CState {
+    tracker: Tracker<usize>,
+    /// the edge back to the parent of an undirected graph's node is the tree edge itself, hence it is skipped once
+    undirected: bool,
+    skipped: HashSet<Node>,
+    cycle: Vec<Node>
+}
+
+impl CState {
+    /// Construct a new `CState` given a `Graph`
+    fn new<W>(g: &Graph<W>) -> CState {
+        CState {
+            tracker: g.get_tracker(Undiscovered, 0, None),
+            undirected: g.kind == GraphKind::Undirected,
+            skipped: HashSet::new(),
+            cycle: Vec::new()
+        }
+    }
+}
+
+impl<W: Weight> DFSearch<W> for CState {
+    type Output = Vec<Node>;
+
+    /// mark node as visited but not processed
+    fn pre_process_node(&mut self, node: Node) -> &mut Self {
+        self.tracker[node].visited(Discovered);
+        self
+    }
+
+    /// mark node as processed, as no cycle passes through it
+    fn post_process_node(&mut self, node: Node) -> &mut Self {
+        self.tracker[node].visited(Processed);
+        self
+    }
+
+    /// an undiscovered `dst` becomes the child of `src`, while a `dst` being explored closes a cycle `dst -> ... -> src -> dst`
+    fn pre_process_edge(&mut self, edge: Edge<W>) -> &mut Self {
+        let Edge(src, dst) = edge;
+        let dst = Node::from(dst);
+        if self.undirected && self.tracker[src].parent == Some(dst) && self.skipped.insert(src) {
+            return self
+        }
+        match self.tracker[dst].visited {
+            Undiscovered => { self.tracker[dst].parent(src); },
+            Discovered => {
+                // walk the parents back from src to dst
+                let mut node = src;
+                self.cycle.push(node);
+                while node != dst {
+                    node = self.tracker[node].parent.expect("dst is an ancestor of src");
+                    self.cycle.push(node);
+                }
+                self.cycle.reverse();
+            },
+            Processed => {}
+        }
+        self
+    }
+
+    /// stop searching once a cycle has been found
+    fn abort(&self) -> bool {
+        !self.cycle.is_empty()
+    }
+
+    /// return the cycle found, if any
+    fn path(&self) -> &Self::Output {
+        &self.cycle
+    }
+
+    /// return true if node is either `Discovered` or `Processed`
+    fn is_discovered(&self, node: Node) -> bool {
+        self.tracker[node].is_discovered()
+    }
+}
+// ANCHOR_END: graphs_find_cycle_state
+// ANCHOR: graphs_find_cycle
+impl<W: Weight> Graph<W> {
+    /// Returns the nodes of a cycle, if the graph contains one, where every node has an edge to the next one
+    /// and the last node to the first one, e.g. `[1, 2, 3]` for `1 -> 2 -> 3 -> 1`
+    /// In an undirected graph the edge leading back to a node's parent is not a cycle, unless it appears twice with different costs
+    pub fn find_cycle(&self) -> Option<Vec<Node>> {
+        let mut cs = CState::new(self);
+        for &start in &self.nodes {
+            if !cs.tracker[start].is_discovered() && cs.path_search(self, start).is_none() {
+                return Some(cs.cycle)
+            }
+        }
+        None
+    }
+}
+// ANCHOR_END: graphs_find_cycle
 
 #[cfg(test)]
 mod test {
-    use std::cmp::Reverse;
+    use std::{cmp::Reverse, fs::File, io::BufReader};
     use super::*;
 
     #[test]
@@ -314,23 +585,40 @@ mod test {
         test_data.into_iter()
             .for_each(|(fname, cuts)| {
                 println!("> {fname}");
-                let g = Graph::import_text_graph(fname, ' ', '\0').unwrap_or_else(|| panic!("Cannot open file: {}",fname));
-
-                let mut scc = g.strongly_connected();
-                scc.sort_by_key(|a| Reverse(a.len()));
-
-                let vec = scc
-                    .into_iter()
-                    .map(|v| v.len() )
-                    .take(5)
-                    .enumerate()
-                    .fold(vec![0;5], |mut out, (idx, val)| { out[idx] = val; out });
-                println!("Found: {:?}, Expected {:?}",vec,cuts);
-                assert_eq!( vec, cuts );
+                let g: Graph = Graph::import_text_graph(BufReader::new(File::open(fname).unwrap_or_else(|e| panic!("Cannot open file: {fname}: {e}"))), ' ', '\0', GraphKind::Directed)
+                    .unwrap_or_else(|e| panic!("Cannot load graph: {fname}: {e}"));
+
+                for mut scc in [g.strongly_connected(), g.strongly_connected_tarjan()].map(|scc| scc.expect("graph is directed")) {
+                    scc.sort_by_key(|a| Reverse(a.len()));
+
+                    let vec = scc
+                        .into_iter()
+                        .map(|v| v.len() )
+                        .take(5)
+                        .enumerate()
+                        .fold(vec![0;5], |mut out, (idx, val)| { out[idx] = val; out });
+                    println!("Found: {:?}, Expected {:?}",vec,cuts);
+                    assert_eq!( vec, cuts );
+                }
                 println!("--------------------");
             });
     }
     #[test]
+    fn test_scc_random() {
+        // both algorithms must partition the nodes the same way, whatever the order of the exit times
+        for seed in 0..20 {
+            let g: Graph = crate::graphs::generate::erdos_renyi(200, 0.008, GraphKind::Directed, seed);
+            let [kosaraju, tarjan] = [g.strongly_connected(), g.strongly_connected_tarjan()]
+                .map(|scc| {
+                    let mut scc = scc.expect("graph is directed");
+                    scc.iter_mut().for_each(|c| c.sort_unstable());
+                    scc.sort_unstable();
+                    scc
+                });
+            assert_eq!(kosaraju, tarjan, "seed {seed}");
+        }
+    }
+    #[test]
     fn test_topological_sort() {
         let test_data = vec![
             ("src/graphs/txt/ts_simple.txt", vec![Some(vec![4, 5, 1, 2, 3, 6]),Some(vec![4, 1, 5, 2, 3, 6])])
@@ -341,11 +629,92 @@ mod test {
         test_data.into_iter()
             .for_each(|(filename, out)| {
                 println!("> {filename}");
-                let g = Graph::import_text_graph(filename, ' ', '\0').unwrap_or_else(|| panic!("Cannot open file: {}", filename));
-                let ts = g.topological_sort();
+                let g: Graph = Graph::import_text_graph(BufReader::new(File::open(filename).unwrap_or_else(|e| panic!("Cannot open file: {filename}: {e}"))), ' ', '\0', GraphKind::Directed)
+                    .unwrap_or_else(|e| panic!("Cannot load graph: {filename}: {e}"));
+                let ts = g.topological_sort().expect("graph is directed");
                 println!("Found: {:?}, Expected {:?}",ts,out);
                 assert!( out.contains(&ts) );
                 println!("--------------------");
             });
     }
+    #[test]
+    fn test_deep_graph() {
+        // a single long chain would overflow the stack of a recursive search
+        const N: Node = 200_000;
+        let chain = (1..N).map(|n| (n, n + 1, 1)).collect::<Vec<_>>();
+        let g = Graph::from_edge_list(&chain, GraphKind::Directed);
+        assert_eq!(g.topological_sort().expect("graph is directed"), Some((1..=N).collect()));
+
+        // closing the chain into a cycle results to a single component
+        let mut g = g;
+        g.insert_edge(N, NC(1, 1));
+        for scc in [g.strongly_connected(), g.strongly_connected_tarjan()].map(|scc| scc.expect("graph is directed")) {
+            assert_eq!(scc.len(), 1);
+            assert_eq!(scc[0].len(), N);
+        }
+    }
+    #[test]
+    fn test_condensation() {
+        // two cycles, 1 -> 2 -> 3 -> 1 and 4 -> 5 -> 4, joined by the edges 3 -> 4 and 2 -> 5, plus a sink node 6
+        let g = Graph::from_edge_list(&[
+            (1, 2, 1), (2, 3, 1), (3, 1, 1), (4, 5, 1), (5, 4, 1), (3, 4, 2), (2, 5, 2), (5, 6, 3)
+        ], GraphKind::Directed);
+
+        // components are reported in reverse topological order
+        let scc = g.strongly_connected_tarjan().expect("graph is directed")
+            .into_iter()
+            .map(|mut c| { c.sort(); c })
+            .collect::<Vec<_>>();
+        assert_eq!(scc, vec![vec![6], vec![4, 5], vec![1, 2, 3]]);
+
+        let (dag, component) = g.condensation().expect("graph is directed");
+        assert_eq!(component, HashMap::from([(1, 0), (2, 0), (3, 0), (4, 1), (5, 1), (6, 2)]));
+        assert_eq!(dag.nodes, HashSet::from([0, 1, 2]));
+        assert_eq!(dag.export_edges(), HashSet::from([Edge(0, NC(1, 2)), Edge(1, NC(2, 3))]));
+        assert_eq!(dag.topological_sort().expect("graph is directed"), Some(vec![0, 1, 2]));
+    }
+    #[test]
+    fn test_find_cycle() {
+        // every consecutive pair of nodes, incl. last -> first, must be an edge of the graph
+        let is_cycle = |g: &Graph, cycle: &[Node]| (0..cycle.len())
+            .all(|i| g.neighbors(cycle[i]).any(|n| n == cycle[(i + 1) % cycle.len()]));
+
+        let g: Graph = Graph::import_text_graph(BufReader::new(File::open("src/graphs/txt/scc_simple.txt").expect("cannot open file")), ' ', '\0', GraphKind::Directed)
+            .expect("graph couldn't be loaded");
+        let cycle = g.find_cycle().expect("graph has cycles");
+        assert!(is_cycle(&g, &cycle));
+        match g.topological_order() {
+            Err(GraphError::Cycle { cycle }) => assert!(is_cycle(&g, &cycle)),
+            other => panic!("Expected cycle, got {:?}", other)
+        }
+
+        let g: Graph = Graph::import_text_graph(BufReader::new(File::open("src/graphs/txt/ts_da_small.txt").expect("cannot open file")), ' ', '\0', GraphKind::Directed)
+            .expect("graph couldn't be loaded");
+        assert_eq!(g.find_cycle(), None);
+        assert_eq!(g.topological_order().ok(), g.topological_sort().expect("graph is directed"));
+
+        // an undirected path has no cycles, until its ends are joined
+        let mut g = Graph::from_edge_list(&[(1, 2, 1), (2, 3, 1), (3, 4, 1)], GraphKind::Undirected);
+        assert_eq!(g.find_cycle(), None);
+        g.insert_edge(4, NC(1, 1));
+        let mut cycle = g.find_cycle().expect("graph has a cycle");
+        assert!(is_cycle(&g, &cycle));
+        cycle.sort();
+        assert_eq!(cycle, vec![1, 2, 3, 4]);
+
+        // self-loops and parallel undirected edges are cycles too
+        let g = Graph::from_edge_list(&[(1, 2, 1), (2, 2, 1)], GraphKind::Directed);
+        assert_eq!(g.find_cycle(), Some(vec![2]));
+        let g = Graph::from_edge_list(&[(1, 2, 1), (1, 2, 3)], GraphKind::Undirected);
+        assert_eq!(g.find_cycle().map(|c| c.len()), Some(2));
+    }
+    #[test]
+    fn test_reject_undirected() {
+        let g = Graph::from_edge_list(&[(1, 2, 1), (2, 3, 1)], GraphKind::Undirected);
+        assert!(matches!(g.strongly_connected(), Err(GraphError::KindMismatch { .. })));
+        assert!(matches!(g.strongly_connected_tarjan(), Err(GraphError::KindMismatch { .. })));
+        assert!(matches!(g.condensation(), Err(GraphError::KindMismatch { .. })));
+        assert!(matches!(g.topological_sort(), Err(GraphError::KindMismatch { .. })));
+        assert!(matches!(g.topological_order(), Err(GraphError::KindMismatch { .. })));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,552 @@
+use std::collections::BTreeSet;
+use super::{*, path_search::{BFSearch, ConstrainedPathSearch, PathConstraints, PathSearch, PSState}, scc::TopologicalSort, NodeState::{Discovered, Undiscovered}};
+
+/// Minimum cost paths from a start node to every node it can reach, as found by a single source search
+/// Built upon the search's `Tracker`, hence any number of goals can be queried without repeating the search
+#[derive(Debug)]
+pub struct ShortestPathTree<W = Cost> {
+    start: Node,
+    tracker: Tracker<W>,
+    /// reachable nodes ordered by increasing cost
+    order: Vec<Node>
+}
+
+impl<W: Weight> ShortestPathTree<W> {
+    /// Constructs the tree given a tracker where reachable nodes are marked as discovered
+    fn new(start: Node, tracker: Tracker<W>) -> ShortestPathTree<W> {
+        let mut order = tracker.list.iter()
+            .filter(|(_, track)| track.is_discovered())
+            .map(|(&node, track)| (track.dist, node))
+            .collect::<Vec<_>>();
+        order.sort_unstable();
+        ShortestPathTree { start, tracker, order: order.into_iter().map(|(_, node)| node).collect() }
+    }
+    /// The node all paths start from
+    pub fn start(&self) -> Node {
+        self.start
+    }
+    /// Minimum cost from the start to `node`, or `None` if `node` cannot be reached
+    pub fn distance_to(&self, node: Node) -> Option<W> {
+        self.tracker.list.get(&node)
+            .filter(|track| track.is_discovered())
+            .map(|track| track.dist)
+    }
+    /// Minimum cost path from the start to `node`, or `None` if `node` cannot be reached
+    pub fn path_to(&self, node: Node) -> Option<Vec<Node>> {
+        self.distance_to(node)
+            .map(|_| self.tracker.extract_path(node))
+    }
+    /// Iterates over the reachable nodes, including the start, and their cost in increasing cost order
+    pub fn iter(&self) -> impl Iterator<Item=(Node, W)> + '_ {
+        self.order.iter().map(|&node| (node, self.tracker[node].dist))
+    }
+    /// The underlying tracker holding each node's cost and parent node
+    pub fn tracker(&self) -> &Tracker<W> {
+        &self.tracker
+    }
+}
+
+/// All-pairs shortest paths, that is, the minimum cost between every pair of nodes
+/// along with the successor of each node towards every destination, so that any path can be reconstructed
+/// Nodes are indexed in ascending order, hence row & column `i` of the matrices correspond to `nodes()[i]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllPairs<W = Cost> {
+    nodes: Vec<Node>,
+    /// `n x n` minimum costs, with `W::MAX` for unreachable pairs
+    dist: Vec<W>,
+    /// `n x n` index of the node following `src` on the path `src -> dst`, if any
+    next: Vec<Option<usize>>
+}
+
+impl<W: Weight> AllPairs<W> {
+    /// Constructs the matrices for the given nodes with every pair unreachable but for a node to itself
+    fn new(g: &Graph<W>) -> AllPairs<W> {
+        let mut nodes = g.nodes.iter().copied().collect::<Vec<_>>();
+        nodes.sort_unstable();
+        let n = nodes.len();
+        let mut ap = AllPairs { nodes, dist: vec![W::MAX; n * n], next: vec![None; n * n] };
+        (0..n).for_each(|i| {
+            ap.dist[i * n + i] = W::ZERO;
+            ap.next[i * n + i] = Some(i);
+        });
+        ap
+    }
+    fn index(&self, node: Node) -> Option<usize> {
+        self.nodes.binary_search(&node).ok()
+    }
+    fn pos(&self, src: Node, dst: Node) -> Option<usize> {
+        Some(self.index(src)? * self.nodes.len() + self.index(dst)?)
+    }
+    /// Nodes in the order used by the matrix rows & columns
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+    /// Minimum cost of the path `src -> dst`, or `None` if `dst` cannot be reached
+    pub fn distance(&self, src: Node, dst: Node) -> Option<W> {
+        let pos = self.pos(src, dst)?;
+        self.next[pos].map(|_| self.dist[pos])
+    }
+    /// The node following `src` on the minimum cost path `src -> dst`; `src` itself when `src == dst`
+    pub fn successor(&self, src: Node, dst: Node) -> Option<Node> {
+        self.next[self.pos(src, dst)?].map(|idx| self.nodes[idx])
+    }
+    /// Reconstructs the minimum cost path `src -> dst` by following successors
+    pub fn extract_path(&self, src: Node, dst: Node) -> Option<Vec<Node>> {
+        let mut path = vec![src];
+        let mut cur = src;
+        while cur != dst {
+            cur = self.successor(cur, dst)?;
+            path.push(cur);
+        }
+        Some(path)
+    }
+    /// Distance matrix with `None` for unreachable pairs
+    pub fn distances(&self) -> Vec<Vec<Option<W>>> {
+        self.nodes.iter()
+            .map(|&src| self.nodes.iter().map(|&dst| self.distance(src, dst)).collect())
+            .collect()
+    }
+}
+
+impl<W: Weight> Graph<W> {
+    // ANCHOR: graphs_bellman_ford_rounds
+    /// Bellman-Ford relaxation rounds given a tracker where the start nodes are `Discovered` at their initial cost
+    /// Returns the tracker holding the minimum costs found, or `GraphError::NegativeCycle` when
+    /// a negative cost cycle is reachable from any of the start nodes
+    fn bellman_ford(&self, mut tracker: Tracker<W>) -> Result<Tracker<W>, GraphError> {
+        let edges = self.edges.iter()
+            .flat_map(|(&src, dst_nodes)| dst_nodes.iter().map(move |&dst| (src, Node::from(dst), dst.cost())))
+            .collect::<Vec<_>>();
+
+        // relax all edges and return the last node whose cost was lowered, if any
+        let relax = |tracker: &mut Tracker<W>| {
+            edges.iter()
+                .fold(None, |relaxed, &(src, dst, cost)| {
+                    // nodes not reached yet have no cost to extend
+                    if !tracker[src].is_discovered() { return relaxed }
+                    let edge_cost = tracker[src].dist + cost;
+                    if edge_cost < tracker[dst].dist {
+                        tracker[dst].visited(Discovered).distance(edge_cost).parent(src);
+                        Some(dst)
+                    } else {
+                        relaxed
+                    }
+                })
+        };
+
+        // with no negative cycles, a minimum cost path has at most N-1 edges hence N-1 rounds suffice
+        for _ in 1..self.nodes.len() {
+            if relax(&mut tracker).is_none() {
+                return Ok(tracker)
+            }
+        }
+        // a cost still lowering at round N means there is a negative cycle upstream of the node
+        let Some(mut node) = relax(&mut tracker) else { return Ok(tracker) };
+
+        // walking back N parents from the node ensures we land onto the cycle
+        for _ in 0..self.nodes.len() {
+            node = tracker[node].parent.expect("bellman_ford(): relaxed node must have a parent");
+        }
+        // walk the cycle backwards until we return to the node, then reverse into edge order
+        let mut cycle = vec![node];
+        let mut cur = tracker[node].parent.expect("bellman_ford(): cycle node must have a parent");
+        while cur != node {
+            cycle.push(cur);
+            cur = tracker[cur].parent.expect("bellman_ford(): cycle node must have a parent");
+        }
+        cycle.reverse();
+        Err(GraphError::NegativeCycle { cycle })
+    }
+    // ANCHOR_END: graphs_bellman_ford_rounds
+}
+
+/// Single source and all-pairs shortest paths, that is, the minimum cost path from a start node to every other node
+pub trait ShortestPaths<W = Cost> {
+    /// Dijkstra's shortest paths from `start` to every reachable node; edge costs must not be negative
+    fn shortest_path_tree(&self, start: Node) -> ShortestPathTree<W>;
+    /// Bellman-Ford shortest paths from `start` to every reachable node, which unlike Dijkstra's copes with negative edge costs
+    /// Unweighted edges cost `W::ONE`, see `NodeType::cost()`
+    /// Returns `GraphError::NegativeCycle` when a negative cost cycle is reachable from `start`
+    fn path_bellman_ford(&self, start: Node) -> Result<ShortestPathTree<W>, GraphError>;
+    /// Floyd–Warshall all-pairs shortest paths in `O(N^3)`, best suited to dense graphs
+    /// Returns `GraphError::NegativeCycle` when the graph contains a negative cost cycle
+    fn all_pairs_floyd_warshall(&self) -> Result<AllPairs<W>, GraphError>;
+    /// Johnson's all-pairs shortest paths in `O(N*E*log(N))`, best suited to sparse graphs
+    /// Edge costs are made non-negative via Bellman-Ford, so that Dijkstra can run from every node
+    /// Returns `GraphError::NegativeCycle` when the graph contains a negative cost cycle
+    fn all_pairs_johnson(&self) -> Result<AllPairs<W>, GraphError>;
+    /// Yen's `k` shortest loopless paths from `start` to `goal` in non-decreasing cost order
+    /// Every path found is used to derive alternative routes, by searching again from each of its nodes
+    /// while skipping the edges already taken from that point, and the nodes before it, via `PathConstraints`
+    /// Returns fewer than `k` paths when no more loopless paths exist
+    fn k_shortest_paths(&self, start: Node, goal: Node, k: usize) -> Vec<(Vec<Node>, W)>;
+    /// Minimum cost paths from `start` on a directed acyclic graph, in `O(N+E)` time
+    /// Edges are relaxed in topological order, hence negative costs are allowed while unweighted edges cost `W::ONE`
+    /// Returns `GraphError::Cycle` when the graph contains a cycle
+    fn dag_shortest_paths(&self, start: Node) -> Result<ShortestPathTree<W>, GraphError>;
+    /// Maximum cost path of a directed acyclic graph, starting from any node, along with its cost, in `O(N+E)` time
+    /// Also known as the critical path, e.g. the chain of tasks determining the duration of a build pipeline
+    /// Edges are relaxed in topological order while unweighted edges cost `W::ONE`; an empty graph has an empty path
+    /// Returns `GraphError::Cycle` when the graph contains a cycle
+    fn dag_longest_path(&self) -> Result<(Vec<Node>, W), GraphError>;
+}
+
+impl<W: Weight> ShortestPaths<W> for Graph<W> {
+    // ANCHOR: graphs_shortest_path_tree
+    fn shortest_path_tree(&self, start: Node) -> ShortestPathTree<W> {
+        // run `path_shortest()`'s search with no goal, so that every node reachable from `start` is settled
+        let mut ps = PSState::new(self);
+        ps.traverse(self, start, None);
+        ShortestPathTree::new(start, ps.tracker)
+    }
+    // ANCHOR_END: graphs_shortest_path_tree
+    // ANCHOR: graphs_bellman_ford
+    fn path_bellman_ford(&self, start: Node) -> Result<ShortestPathTree<W>, GraphError> {
+        let mut tracker = self.get_tracker(Undiscovered, W::MAX, None);
+        tracker[start].visited(Discovered).distance(W::ZERO);
+        Ok(ShortestPathTree::new(start, self.bellman_ford(tracker)?))
+    }
+    // ANCHOR_END: graphs_bellman_ford
+    // ANCHOR: graphs_floyd_warshall
+    fn all_pairs_floyd_warshall(&self) -> Result<AllPairs<W>, GraphError> {
+        let mut ap = AllPairs::new(self);
+        let n = ap.nodes.len();
+
+        // direct edges; keep the cheapest one among parallel edges
+        for (&src, edges) in &self.edges {
+            let i = ap.index(src).expect("all_pairs_floyd_warshall(): edge source isn't a graph node");
+            for &dst in edges {
+                let (dst, cost) = (dst.into(), dst.cost());
+                let j = ap.index(dst).expect("all_pairs_floyd_warshall(): edge destination isn't a graph node");
+                if cost < ap.dist[i * n + j] || ap.next[i * n + j].is_none() {
+                    ap.dist[i * n + j] = cost;
+                    ap.next[i * n + j] = Some(j);
+                }
+            }
+        }
+        // allow paths to go through node `k`, one node at a time
+        for k in 0..n {
+            for i in 0..n {
+                if ap.next[i * n + k].is_none() { continue }
+                for j in 0..n {
+                    if ap.next[k * n + j].is_none() { continue }
+                    let cost = ap.dist[i * n + k] + ap.dist[k * n + j];
+                    if ap.next[i * n + j].is_none() || cost < ap.dist[i * n + j] {
+                        ap.dist[i * n + j] = cost;
+                        ap.next[i * n + j] = ap.next[i * n + k];
+                    }
+                }
+            }
+            // a node that can reach itself at a negative cost sits on a negative cycle
+            if let Some(i) = (0..n).find(|&i| ap.dist[i * n + i] < W::ZERO) {
+                return Err(self.path_bellman_ford(ap.nodes[i]).expect_err("all_pairs_floyd_warshall(): node must be on a negative cycle"))
+            }
+        }
+        Ok(ap)
+    }
+    // ANCHOR_END: graphs_floyd_warshall
+    // ANCHOR: graphs_johnson
+    fn all_pairs_johnson(&self) -> Result<AllPairs<W>, GraphError> {
+        let mut ap = AllPairs::new(self);
+
+        // Step 1: find the minimum cost `h(node)` from a virtual node with a zero cost edge to every node,
+        // which is `ZERO` or negative; that's the same as starting Bellman-Ford with every node at zero cost
+        let tracker = self.bellman_ford(self.get_tracker(Discovered, W::ZERO, None))?;
+        let h = |node: Node| tracker[node].dist;
+
+        // Step 2: reweight every edge as `cost + h(src) - h(dst)`, which is never negative
+        let mut reweighted = Graph::new(self.kind);
+        reweighted.nodes.clone_from(&self.nodes);
+        for (&src, edges) in &self.edges {
+            reweighted.edges.insert(src, edges.iter()
+                .map(|&dst| NC(dst.into(), dst.cost() + h(src) - h(dst.into())))
+                .collect()
+            );
+        }
+
+        // Step 3: run Dijkstra from every node and revert the reweighting, that is, `dist - h(src) + h(dst)`
+        for &src in &self.nodes {
+            let tree = reweighted.shortest_path_tree(src);
+            // the successor of `src` towards a node is shared by every node on the way up the tree
+            let mut next = HashMap::from([(src, src)]);
+            for (dst, cost) in tree.iter() {
+                let mut chain = Vec::new();
+                let mut cur = dst;
+                let succ = loop {
+                    if let Some(&succ) = next.get(&cur) { break succ }
+                    chain.push(cur);
+                    let parent = tree.tracker()[cur].parent.expect("all_pairs_johnson(): reached node must have a parent");
+                    // the node right after `src` is the successor
+                    if parent == src { break cur }
+                    cur = parent;
+                };
+                chain.into_iter().for_each(|node| { next.insert(node, succ); });
+
+                let pos = ap.pos(src, dst).expect("all_pairs_johnson(): tree node isn't a graph node");
+                ap.dist[pos] = cost - h(src) + h(dst);
+                ap.next[pos] = ap.index(next[&dst]);
+            }
+        }
+        Ok(ap)
+    }
+    // ANCHOR_END: graphs_johnson
+    // ANCHOR: graphs_yen
+    fn k_shortest_paths(&self, start: Node, goal: Node, k: usize) -> Vec<(Vec<Node>, W)> {
+        // cost of a path following the cheapest edge between every pair of nodes, as Dijkstra's search does
+        let path_cost = |path: &[Node]| path.windows(2)
+            .map(|e| self.edges[&e[0]].iter()
+                .filter(|&&dst| Node::from(dst) == e[1])
+                .map(|dst| dst.cost())
+                .min()
+                .expect("path follows existing edges")
+            )
+            .fold(W::ZERO, |total, cost| total + cost);
+
+        let mut found = Vec::new();
+        if k == 0 { return found }
+        let Some(first) = self.path_shortest(start, goal) else { return found };
+        found.push(first);
+        // candidate paths ordered by cost; a set also drops the same path derived more than once
+        let mut candidates = BTreeSet::new();
+
+        while found.len() < k {
+            let (last, _) = found.last().expect("at least one path found");
+            for i in 0..last.len() - 1 {
+                // search from the spur node onwards, having the root path leading to it fixed
+                let (spur, root) = (last[i], &last[..=i]);
+                // skip the edges taken from the spur node by the paths sharing the same root ...
+                let taken = found.iter()
+                    .filter(|(path, _)| path.len() > i + 1 && path[..=i] == *root)
+                    .map(|(path, _)| path[i + 1])
+                    .collect::<HashSet<_>>();
+                // ... and the root nodes before the spur node, so that the path remains loopless
+                let constraints = PathConstraints::new()
+                    .exclude(root[..i].iter().copied())
+                    .edge_filter(|src, dst| src != spur || !taken.contains(&dst.into()));
+
+                if let Some((spur_path, spur_cost)) = self.path_shortest_constrained(spur, goal, &constraints) {
+                    let mut path = root[..i].to_vec();
+                    path.extend(spur_path);
+                    candidates.insert((path_cost(root) + spur_cost, path));
+                }
+            }
+            match candidates.pop_first() {
+                Some((cost, path)) => found.push((path, cost)),
+                None => break
+            }
+        }
+        found
+    }
+    // ANCHOR_END: graphs_yen
+    // ANCHOR: graphs_dag_paths
+    fn dag_shortest_paths(&self, start: Node) -> Result<ShortestPathTree<W>, GraphError> {
+        let order = self.topological_order()?;
+
+        // reached nodes are `Discovered`, and by the time a node is visited in topological order
+        // all edges leading to it have been relaxed, hence its cost is final
+        let mut tracker = self.get_tracker(Undiscovered, W::MAX, None);
+        tracker[start].visited(Discovered).distance(W::ZERO);
+        for src in order {
+            if !tracker[src].is_discovered() { continue }
+            for &dst in self.edges.get(&src).into_iter().flatten() {
+                let cost = tracker[src].dist + dst.cost();
+                if cost < tracker[dst.into()].dist {
+                    tracker[dst.into()].visited(Discovered).distance(cost).parent(src);
+                }
+            }
+        }
+        Ok(ShortestPathTree::new(start, tracker))
+    }
+    fn dag_longest_path(&self) -> Result<(Vec<Node>, W), GraphError> {
+        let order = self.topological_order()?;
+
+        // every node can start a path, hence all costs start at zero
+        // and each node keeps the maximum cost path leading to it
+        let mut tracker = self.get_tracker(Undiscovered, W::ZERO, None);
+        for &src in &order {
+            for &dst in self.edges.get(&src).into_iter().flatten() {
+                let cost = tracker[src].dist + dst.cost();
+                if cost > tracker[dst.into()].dist {
+                    tracker[dst.into()].distance(cost).parent(src);
+                }
+            }
+        }
+        // the longest path ends at the node with the maximum cost
+        Ok(order.into_iter()
+            .max_by_key(|&node| tracker[node].dist)
+            .map_or((vec![], W::ZERO), |end| tracker.extract(end))
+        )
+    }
+    // ANCHOR_END: graphs_dag_paths
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs::File, io::BufReader};
+    use crate::graphs::generate;
+    use super::*;
+
+    fn load(filename: &str, node_pat: char, edge_pat: char) -> Graph {
+        Graph::import_text_graph(BufReader::new(File::open(filename).expect("cannot open file")), node_pat, edge_pat, GraphKind::Directed)
+            .expect("graph couldn't be loaded")
+    }
+
+    #[test]
+    fn test_shortest_path_tree() {
+        let g = load("src/graphs/txt/ps_input_random_10_16.txt", '\t', ',');
+        let tree = g.shortest_path_tree(1);
+        assert_eq!(tree.start(), 1);
+        [7, 37, 59, 82, 99, 115, 133, 165, 188, 197].into_iter()
+            .for_each(|goal| {
+                let path = g.path_shortest(1, goal);
+                assert_eq!(tree.distance_to(goal), path.as_ref().map(|&(_, c)| c));
+                assert_eq!(tree.path_to(goal).map(|p| (p.first().copied(), p.last().copied())), Some((Some(1), Some(goal))));
+            });
+        // nodes are visited in increasing cost order, starting with the start node itself
+        let costs = tree.iter().map(|(_, c)| c).collect::<Vec<_>>();
+        assert!(costs.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(tree.iter().next(), Some((1, 0)));
+        assert_eq!(tree.iter().count(), g.nodes.len());
+
+        let g = Graph::from_edge_list(&[(1, 2, 1), (3, 2, 1)], GraphKind::Directed);
+        let tree = g.shortest_path_tree(1);
+        assert_eq!(tree.distance_to(3), None);
+        assert_eq!(tree.path_to(3), None);
+        assert_eq!(tree.path_to(4), None);
+        assert_eq!(tree.iter().collect::<Vec<_>>(), vec![(1, 0), (2, 1)]);
+    }
+    #[test]
+    fn test_bellman_ford() {
+        let g = Graph::from_edge_list(&[(1, 2, 4), (1, 3, 2), (3, 2, -3), (2, 4, 1), (4, 5, -2), (6, 5, 1)], GraphKind::Directed);
+        let tree = g.path_bellman_ford(1).expect("no negative cycles");
+        assert_eq!(tree.path_to(5), Some(vec![1, 3, 2, 4, 5]));
+        assert_eq!(tree.distance_to(5), Some(-2));
+        assert_eq!(tree.distance_to(2), Some(-1));
+        assert_eq!(tree.distance_to(6), None);
+        assert_eq!(tree.iter().map(|(n, _)| n).collect::<Vec<_>>(), vec![5, 2, 1, 4, 3]);
+
+        // with no negative costs the result matches Dijkstra's
+        let g = load("src/graphs/txt/ps_input_random_10_16.txt", '\t', ',');
+        let tree = g.path_bellman_ford(1).expect("no negative cycles");
+        let dijkstra = g.shortest_path_tree(1);
+        assert_eq!(tree.iter().map(|(_, c)| c).collect::<Vec<_>>(), dijkstra.iter().map(|(_, c)| c).collect::<Vec<_>>());
+
+        // unweighted edges cost one
+        let g: Graph = generate::grid(3, 4, GraphKind::Directed);
+        assert_eq!(g.path_bellman_ford(1).expect("no negative cycles").distance_to(12), Some(5));
+        assert_eq!(g.all_pairs_floyd_warshall().expect("no negative cycles").distance(1, 12), Some(5));
+        assert_eq!(g.all_pairs_johnson().expect("no negative cycles").distance(1, 12), Some(5));
+    }
+    #[test]
+    fn test_all_pairs() {
+        let g = load("src/graphs/txt/ps_input_random_10_16.txt", '\t', ',');
+        let fw = g.all_pairs_floyd_warshall().expect("no negative cycles");
+        let jn = g.all_pairs_johnson().expect("no negative cycles");
+        assert_eq!(fw.distances(), jn.distances());
+        for (src, dst) in [(1, 7), (1, 197), (37, 59), (200, 1)] {
+            let expected = g.path_shortest(src, dst).map(|(_, c)| c);
+            assert_eq!(fw.distance(src, dst), expected);
+            // the reconstructed path must cost as much as the distance
+            let path = jn.extract_path(src, dst).expect("path exists");
+            let cost = path.windows(2)
+                .map(|e| g.edges[&e[0]].iter().filter_map(|&nt| match nt { NC(n, c) if n == e[1] => Some(c), _ => None }).min().unwrap())
+                .sum::<Cost>();
+            assert_eq!(Some(cost), expected);
+        }
+
+        let g = Graph::from_edge_list(&[(1, 2, 4), (1, 3, 2), (3, 2, -3), (2, 4, 1), (4, 5, -2), (6, 5, 1)], GraphKind::Directed);
+        for ap in [g.all_pairs_floyd_warshall(), g.all_pairs_johnson()] {
+            let ap = ap.expect("no negative cycles");
+            assert_eq!(ap.distance(1, 5), Some(-2));
+            assert_eq!(ap.extract_path(1, 5), Some(vec![1, 3, 2, 4, 5]));
+            assert_eq!(ap.successor(1, 4), Some(3));
+            assert_eq!(ap.distance(5, 1), None);
+            assert_eq!(ap.extract_path(5, 1), None);
+            assert_eq!(ap.distance(6, 6), Some(0));
+        }
+
+        let g = Graph::from_edge_list(&[(1, 2, 1), (2, 3, 2), (3, 4, -4), (4, 2, 1), (4, 5, 1)], GraphKind::Directed);
+        assert!(matches!(g.all_pairs_floyd_warshall(), Err(GraphError::NegativeCycle { .. })));
+        assert!(matches!(g.all_pairs_johnson(), Err(GraphError::NegativeCycle { .. })));
+    }
+    #[test]
+    fn test_bellman_ford_negative_cycle() {
+        // 2 -> 3 -> 4 -> 2 costs -1 in total
+        let g = Graph::from_edge_list(&[(1, 2, 1), (2, 3, 2), (3, 4, -4), (4, 2, 1), (4, 5, 1)], GraphKind::Directed);
+        match g.path_bellman_ford(1) {
+            Err(GraphError::NegativeCycle { mut cycle }) => {
+                // rotate so the cycle starts at its smallest node
+                let min = cycle.iter().position(|&n| n == 2).expect("node 2 is on the cycle");
+                cycle.rotate_left(min);
+                assert_eq!(cycle, vec![2, 3, 4]);
+            }
+            other => panic!("Expected negative cycle, got {:?}", other)
+        }
+        // the cycle is not reachable from node 5
+        assert!(g.path_bellman_ford(5).is_ok());
+    }
+    #[test]
+    fn test_k_shortest_paths() {
+        // the classic example from Yen's algorithm, with C, D, E, F, G, H as nodes 1 to 6
+        let g = Graph::from_edge_list(&[
+            (1, 2, 3), (1, 3, 2), (2, 4, 4), (3, 2, 1), (3, 4, 2), (3, 5, 3), (4, 5, 2), (4, 6, 1), (5, 6, 2)
+        ], GraphKind::Directed);
+        assert_eq!(g.k_shortest_paths(1, 6, 3), vec![
+            (vec![1, 3, 4, 6], 5),
+            (vec![1, 3, 5, 6], 7),
+            (vec![1, 2, 4, 6], 8)
+        ]);
+        // only 7 loopless paths exist
+        let paths = g.k_shortest_paths(1, 6, 10);
+        assert_eq!(paths.len(), 7);
+        assert!(paths.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert!(paths.iter().all(|(path, _)| path.iter().collect::<HashSet<_>>().len() == path.len()));
+        assert_eq!(g.k_shortest_paths(1, 6, 0), vec![]);
+        assert_eq!(g.k_shortest_paths(6, 1, 3), vec![]);
+
+        // unweighted edges cost one, hence all 3 paths across a 2 x 3 grid cost the same
+        let g: Graph = generate::grid(2, 3, GraphKind::Directed);
+        let paths = g.k_shortest_paths(1, 6, 5);
+        assert_eq!(paths.len(), 3);
+        assert!(paths.iter().all(|(path, cost)| path.len() == 4 && *cost == 3));
+
+        let g = load("src/graphs/txt/ps_input_random_10_16.txt", '\t', ',');
+        let paths = g.k_shortest_paths(1, 197, 5);
+        assert_eq!(paths.len(), 5);
+        assert_eq!(paths.first().cloned(), g.path_shortest(1, 197));
+        assert!(paths.windows(2).all(|w| w[0].1 <= w[1].1 && w[0].0 != w[1].0));
+    }
+    #[test]
+    fn test_dag_paths() {
+        // build pipeline where each edge costs the duration of the source task
+        let g = Graph::from_edge_list(&[
+            (1, 2, 3), (1, 3, 3), (2, 4, 2), (3, 4, 5), (4, 5, 1), (3, 6, 5), (6, 5, -1)
+        ], GraphKind::Directed);
+        let tree = g.dag_shortest_paths(1).expect("graph is acyclic");
+        assert_eq!(tree.path_to(5), Some(vec![1, 2, 4, 5]));
+        assert_eq!(tree.distance_to(5), Some(6));
+        assert_eq!(tree.distance_to(6), Some(8));
+        assert_eq!(g.dag_shortest_paths(3).expect("graph is acyclic").distance_to(2), None);
+        assert_eq!(g.dag_longest_path().expect("graph is acyclic"), (vec![1, 3, 4, 5], 9));
+
+        // matches Dijkstra's when costs are non-negative
+        let g = generate::with_random_costs(&generate::random_dag::<Cost>(100, 0.1, 7), 1..50, 7);
+        let (tree, dijkstra) = (g.dag_shortest_paths(1).expect("graph is acyclic"), g.shortest_path_tree(1));
+        assert!(g.nodes.iter().all(|&node| tree.distance_to(node) == dijkstra.distance_to(node)));
+
+        // unweighted edges cost one, hence the longest path has the most edges
+        let g: Graph = generate::grid(3, 4, GraphKind::Directed);
+        let (path, cost) = g.dag_longest_path().expect("graph is acyclic");
+        assert_eq!((path.first(), path.last(), cost), (Some(&1), Some(&12), 5));
+        assert_eq!(Graph::<Cost>::new(GraphKind::Directed).dag_longest_path().expect("graph is acyclic"), (vec![], 0));
+
+        let g = Graph::from_edge_list(&[(1, 2, 1), (2, 3, 1), (3, 1, 1)], GraphKind::Directed);
+        assert!(matches!(g.dag_shortest_paths(1), Err(GraphError::Cycle { .. })));
+        match g.dag_longest_path() {
+            Err(GraphError::Cycle { mut cycle }) => { cycle.sort(); assert_eq!(cycle, vec![1, 2, 3]) },
+            other => panic!("Expected cycle, got {:?}", other)
+        }
+        let g = Graph::from_edge_list(&[(1, 2, 1)], GraphKind::Undirected);
+        assert!(matches!(g.dag_longest_path(), Err(GraphError::KindMismatch { .. })));
+    }
+}
@@ -0,0 +1,174 @@
+use super::*;
+
+// ANCHOR: graphs_union_find
+/// Disjoint-set forest over graph nodes, that is, a collection of non-overlapping sets of nodes
+/// where each set is represented by one of its nodes, the root
+/// Union by rank along with path compression keeps the trees almost flat,
+/// hence both `find()` and `union()` run in near-constant amortised time
+/// ```
+/// use csx3::graphs::union_find::UnionFind;
+///
+/// let mut sets = [1, 2, 3, 4].into_iter().collect::<UnionFind>();
+/// assert!(sets.union(1, 2));
+/// assert!(sets.union(3, 4));
+/// assert!(!sets.union(2, 1));
+/// assert!(sets.connected(1, 2));
+/// assert!(!sets.connected(1, 4));
+/// assert_eq!(sets.count(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UnionFind {
+    /// parent of every node; a root is its own parent
+    parent: HashMap<Node, Node>,
+    /// upper bound of the tree's height, only maintained for roots
+    rank: HashMap<Node, u32>,
+    /// number of disjoint sets
+    count: usize
+}
+
+impl UnionFind {
+    /// Construct an empty disjoint-set forest
+    pub fn new() -> UnionFind {
+        UnionFind::default()
+    }
+    /// Adds the node as a set of its own; returns `false` if the node is already known
+    pub fn make_set(&mut self, node: Node) -> bool {
+        if self.parent.contains_key(&node) {
+            return false
+        }
+        self.parent.insert(node, node);
+        self.rank.insert(node, 0);
+        self.count += 1;
+        true
+    }
+    /// Number of disjoint sets
+    pub fn count(&self) -> usize {
+        self.count
+    }
+    /// Number of nodes across all sets
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+    /// Are there any nodes ?
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+    /// Returns the root representing the set the node belongs to, or `None` if the node is unknown
+    /// Every node on the way up is re-pointed to the root, so that subsequent queries are faster
+    pub fn find(&mut self, node: Node) -> Option<Node> {
+        let mut root = *self.parent.get(&node)?;
+        while self.parent[&root] != root {
+            root = self.parent[&root];
+        }
+        // path compression
+        let mut cur = node;
+        while cur != root {
+            cur = self.parent.insert(cur, root).expect("node is known");
+        }
+        Some(root)
+    }
+    /// Merges the sets the two nodes belong to, attaching the shorter tree under the taller one
+    /// Returns `false` if the nodes are already in the same set, or either node is unknown
+    pub fn union(&mut self, a: Node, b: Node) -> bool {
+        let (Some(a), Some(b)) = (self.find(a), self.find(b)) else { return false };
+        if a == b {
+            return false
+        }
+        let (low, high) = if self.rank[&a] < self.rank[&b] { (a, b) } else { (b, a) };
+        self.parent.insert(low, high);
+        if self.rank[&low] == self.rank[&high] {
+            *self.rank.get_mut(&high).expect("root has a rank") += 1;
+        }
+        self.rank.remove(&low);
+        self.count -= 1;
+        true
+    }
+    /// Do the two nodes belong to the same set ?
+    pub fn connected(&mut self, a: Node, b: Node) -> bool {
+        matches!((self.find(a), self.find(b)), (Some(a), Some(b)) if a == b)
+    }
+    // ANCHOR_END: graphs_union_find
+    /// Returns the nodes of every set; both the sets and their nodes are in no particular order
+    pub fn sets(&mut self) -> Vec<Vec<Node>> {
+        let nodes = self.parent.keys().copied().collect::<Vec<_>>();
+        nodes.into_iter()
+            .fold(HashMap::<Node, Vec<Node>>::new(), |mut sets, node| {
+                let root = self.find(node).expect("node is known");
+                sets.entry(root).or_default().push(node);
+                sets
+            })
+            .into_values()
+            .collect()
+    }
+}
+
+impl FromIterator<Node> for UnionFind {
+    /// Constructs a forest where every node is a set of its own
+    fn from_iter<T: IntoIterator<Item=Node>>(iter: T) -> Self {
+        let mut sets = UnionFind::new();
+        iter.into_iter().for_each(|node| { sets.make_set(node); });
+        sets
+    }
+}
+
+impl<W: Weight> Graph<W> {
+    /// Connected components of an undirected graph, that is, the sets of nodes that can reach one another
+    /// Every edge merges the sets of its two nodes, hence an isolated node forms a component of its own
+    pub fn connected_components(&self) -> Result<Vec<Vec<Node>>, GraphError> {
+        // directed graphs have strongly connected components instead
+        self.ensure_kind(GraphKind::Undirected)?;
+
+        let mut sets = self.nodes.iter().copied().collect::<UnionFind>();
+        self.edges.iter()
+            .flat_map(|(&src, edges)| edges.iter().map(move |&dst| (src, Node::from(dst))))
+            .for_each(|(src, dst)| { sets.union(src, dst); });
+        Ok(sets.sets())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::generate;
+
+    #[test]
+    fn test_union_find() {
+        let mut sets = (1..=10).collect::<UnionFind>();
+        assert_eq!((sets.len(), sets.count()), (10, 10));
+        assert!(!sets.make_set(1));
+
+        // chain all even nodes, then all odd ones
+        (2..=8).step_by(2).for_each(|n| assert!(sets.union(n, n + 2)));
+        (1..=7).step_by(2).for_each(|n| assert!(sets.union(n + 2, n)));
+        assert_eq!(sets.count(), 2);
+        assert!(sets.connected(2, 10));
+        assert!(!sets.connected(1, 10));
+        assert!(!sets.union(4, 8));
+        assert!(!sets.union(1, 11));
+        assert_eq!(sets.find(11), None);
+
+        // every node points straight to its root once found
+        let root = sets.find(10).expect("node is known");
+        assert!((2..=10).step_by(2).all(|n| sets.parent[&n] == root));
+
+        let mut found = sets.sets();
+        found.iter_mut().for_each(|s| s.sort());
+        found.sort();
+        assert_eq!(found, vec![vec![1, 3, 5, 7, 9], vec![2, 4, 6, 8, 10]]);
+    }
+    #[test]
+    fn test_connected_components() {
+        let mut g = Graph::from_edge_list(&[(1, 2, 1), (2, 3, 1), (4, 5, 1)], GraphKind::Undirected);
+        g.nodes.insert(6);
+        let mut cc = g.connected_components().expect("graph is undirected");
+        cc.iter_mut().for_each(|c| c.sort());
+        cc.sort();
+        assert_eq!(cc, vec![vec![1, 2, 3], vec![4, 5], vec![6]]);
+
+        let g: Graph = generate::grid(10, 10, GraphKind::Undirected);
+        assert_eq!(g.connected_components().expect("graph is undirected").len(), 1);
+
+        let g = Graph::from_edge_list(&[(1, 2, 1)], GraphKind::Directed);
+        assert!(matches!(g.connected_components(), Err(GraphError::KindMismatch { .. })));
+    }
+}
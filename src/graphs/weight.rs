@@ -0,0 +1,110 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display, Formatter},
+    hash::{Hash, Hasher},
+    num::ParseFloatError,
+    ops::{Add, Sub},
+    str::FromStr
+};
+
+/// Edge weight abstraction used by `Graph` and `NodeType::NC`
+/// A weight must be totally ordered and hashable so it can be stored in `HashSet` & `BinaryHeap` structures,
+/// while `ZERO`, `ONE` and `MAX` provide the start, step and "unreachable" values used by the path search algorithms
+pub trait Weight: Copy + Debug + Display + Hash + Eq + Ord + Add<Output=Self> + Sub<Output=Self> + FromStr {
+    const ZERO: Self;
+    const ONE: Self;
+    const MAX: Self;
+}
+
+macro_rules! impl_weight {
+    ( $($t:ty),* ) => {
+        $( impl Weight for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const MAX: Self = <$t>::MAX;
+        } )*
+    }
+}
+impl_weight!(i32, i64, u32, u64, usize);
+
+/// A `f64` wrapper that provides total ordering and hashing, so that it can be used as a `Weight`
+/// Ordering follows `f64::total_cmp()`, hence `NaN` values are ordered after `+∞`
+/// Serializes as a plain `f64`
+#[derive(Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct OrderedFloat(pub f64);
+
+impl Weight for OrderedFloat {
+    const ZERO: Self = OrderedFloat(0.0);
+    const ONE: Self = OrderedFloat(1.0);
+    const MAX: Self = OrderedFloat(f64::INFINITY);
+}
+impl PartialEq for OrderedFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for OrderedFloat {}
+impl PartialOrd for OrderedFloat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for OrderedFloat {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+impl Hash for OrderedFloat {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // consistent with `total_cmp()` equality, that is, bit-wise equality
+        self.0.to_bits().hash(state)
+    }
+}
+impl Add for OrderedFloat {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        OrderedFloat(self.0 + rhs.0)
+    }
+}
+impl Sub for OrderedFloat {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        OrderedFloat(self.0 - rhs.0)
+    }
+}
+impl FromStr for OrderedFloat {
+    type Err = ParseFloatError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        f64::from_str(s).map(OrderedFloat)
+    }
+}
+impl From<f64> for OrderedFloat {
+    fn from(value: f64) -> Self {
+        OrderedFloat(value)
+    }
+}
+impl Debug for OrderedFloat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+impl Display for OrderedFloat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ordered_float() {
+        let mut v = vec![OrderedFloat(2.5), OrderedFloat::MAX, OrderedFloat(-1.0), OrderedFloat::ZERO];
+        v.sort();
+        assert_eq!(v, vec![OrderedFloat(-1.0), OrderedFloat(0.0), OrderedFloat(2.5), OrderedFloat(f64::INFINITY)]);
+        assert_eq!(OrderedFloat(1.5) + OrderedFloat::ONE, OrderedFloat(2.5));
+        assert_eq!("0.25".parse::<OrderedFloat>(), Ok(OrderedFloat(0.25)));
+    }
+}
@@ -1,30 +1,30 @@
 use std::collections::{BinaryHeap};
 use crate::graphs::{
-    Graph, Edge, NodeType::NC,
-    min_cut::SuperNodes
+    Graph, GraphError, GraphKind, Edge, Cost, NodeType::NC,
+    union_find::UnionFind, weight::Weight
 };
 
 // ANCHOR: graphs_mst_cluster_def
-struct ClusterSet {
-    mst: Graph,
-    clusters : SuperNodes
+struct ClusterSet<W = Cost> {
+    mst: Graph<W>,
+    clusters : UnionFind
 }
 
-impl ClusterSet {
+impl<W: Weight> ClusterSet<W> {
     /// spacing of a clustering. It's the distance between the closest together pair of separated points
     /// We want all of the separated points to be as far apart as possible.
     /// That is, we want the spacing to be big. The bigger the better
-    fn spacing(&self) -> Edge {
+    fn spacing(&mut self) -> Edge<W> {
         self.crossing_edges().pop().unwrap_or_else(|| panic!("spacing(): no edges found spanning the clusters"))
     }
-    fn crossing_edges(&self) -> BinaryHeap<Edge>{
+    fn crossing_edges(&mut self) -> BinaryHeap<Edge<W>>{
 
         let mut input = self.mst.get_edges_by_cost();
-        let mut output = BinaryHeap::<Edge>::new();
+        let mut output = BinaryHeap::<Edge<W>>::new();
 
         while let Some(edge) = input.pop() {
             let Edge(src, dst) = edge;
-            if self.clusters.find_supernode(&src) != self.clusters.find_supernode(&dst.into()) {
+            if !self.clusters.connected(src, dst.into()) {
                 output.push(edge);
             }
         }
@@ -34,60 +34,54 @@ impl ClusterSet {
 
 // ANCHOR_END: graphs_mst_cluster_def
 // ANCHOR: graphs_mst_cluster_impl
-trait Clustering {
-    fn find_clusters(&self, k: usize) -> Option<ClusterSet>;
+trait Clustering<W> {
+    fn find_clusters(&self, k: usize) -> Result<Option<ClusterSet<W>>, GraphError>;
 }
 
-impl Clustering for Graph {
+impl<W: Weight> Clustering<W> for Graph<W> {
 
-    fn find_clusters(&self, k: usize) -> Option<ClusterSet> {
+    fn find_clusters(&self, k: usize) -> Result<Option<ClusterSet<W>>, GraphError> {
+
+        // clustering relies on the undirected minimum spanning tree
+        self.ensure_kind(GraphKind::Undirected)?;
 
         // Get the ordered heap by lowest cost Edge on top
         let mut heap = self.get_edges_by_cost();
-        // Keeps the graph's components, that is, a super node is a graph component's lead node
-        // The initial state is for each node to be a lead component node with a component of its own
-        let mut snodes = self.get_super_nodes();
+        // Keeps the graph's components as disjoint sets
+        // The initial state is for each node to be a component of its own
+        let mut components = self.nodes.iter().copied().collect::<UnionFind>();
         // the output graph that will hold *only* the edges
         // that form the minimum spanning tree
-        let mut graph = Graph::new();
+        let mut graph = Graph::new(GraphKind::Undirected);
         let mut clusters = None;
 
         // As long as more than 2 components
-        while snodes.len() > 1 {
+        while components.count() > 1 {
             // get the edge with the lowest cost
             // otherwise if we've run out of edges while there are 2 or more components
             // then the graph IS NOT CONNECTED
-            let Some(edge) = heap.pop() else { return None };
+            let Some(edge) = heap.pop() else { return Ok(None) };
             let Edge(src, NC(dst, _)) = edge else { panic!("find_clusters() - Cannot find NodeType::NC") };
-            // print!("({src:2}->{dst:2}):{cost:6} - ");
-
-            // if src is not a super node then get its super node
-            let src = snodes.find_supernode(&src);
-            // if dst is not a super node then get its super node
-            let dst = snodes.find_supernode(&dst);
 
             // if src component differs from dst component then merge the two and save the edge connecting them
-            if src != dst {
-                snodes.merge_nodes(src, dst);
+            if components.union(src, dst) {
                 graph.push_edge(edge);
-                // println!("Store");
-            } else {
-                // println!("Skip");
             }
-            if snodes.len() == k {
-                clusters = Some(snodes.clone())
+            if components.count() == k {
+                clusters = Some(components.clone())
             }
         }
-        Some(ClusterSet{
+        Ok(Some(ClusterSet{
             mst: graph,
             clusters: clusters.unwrap()
-        })
+        }))
     }
 }
 // ANCHOR_END: graphs_mst_cluster_impl
 
 #[cfg(test)]
 mod test {
+    use std::{fs::File, io::BufReader};
     use super::*;
     #[test]
     fn test_clustering() {
@@ -99,9 +93,11 @@ mod test {
         ];
         for (filename, result) in test_data {
             println!("{filename}");
-            let mut g = Graph::new();
-            let edge = g.load_file_mst(filename)
+            let g: Graph = Graph::import_text_mst(BufReader::new(File::open(filename).expect("cannot open file")))
+                .expect("graph couldn't be loaded");
+            let edge = g
                 .find_clusters(4)
+                .expect("graph is undirected")
                 .unwrap_or_else(|| panic!("Returned None instead of a ClusterSet"))
                 .spacing();
             print!("Edge: {:?}", edge);
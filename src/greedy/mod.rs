@@ -1,41 +1,34 @@
 mod mst;
 mod cluster;
 
-use std::{ cmp::Ordering, collections::BinaryHeap };
-use crate::graphs::{ Edge, Graph, Cost, NodeType::{N, NC} };
+use std::{ cmp::Ordering, collections::BinaryHeap, io::BufRead, str::FromStr };
+use crate::graphs::{ Edge, Graph, GraphError, GraphKind, ParseErrorKind, Node, NodeType::{N, NC}, weight::Weight };
 
-impl Graph {
+impl<W: Weight> Graph<W> {
     // ANCHOR: graphs_mst_graph
     /// Sums up the cost of all weighted edges
-    pub fn sum_edges(&self) -> Cost {
+    pub fn sum_edges(&self) -> W {
         self.edges
-            .values()
-            .fold(0, |cost, edges| {
-                cost + edges.iter()
+            .iter()
+            .fold(W::ZERO, |cost, (&src, edges)| {
+                edges.iter()
                     .map(|&dst| {
-                        let NC(_,c) = dst else { panic!("get_mst_cost(): Edge destination node is not of type NodeType::NC") };
-                        c
+                        let NC(dst,c) = dst else { panic!("get_mst_cost(): Edge destination node is not of type NodeType::NC") };
+                        (dst,c)
                     })
-                    .reduce(|acc,c| acc + c )
-                    .unwrap()
-            }) >> 1 // in an undirected graph we count twice the edge hence dividing by 2
+                    // in an undirected graph each edge is stored twice hence we only count src -> dst, with src <= dst
+                    .filter(|&(dst,_)| self.kind == GraphKind::Directed || src <= dst)
+                    .fold(cost, |cost, (_,c)| cost + c)
+            })
     }
-    /// Adds a new Edge to the graph
-    pub fn push_edge(&mut self, edge: Edge) {
+    /// Adds a new Edge to the graph; the edge is mirrored when the graph is undirected
+    pub fn push_edge(&mut self, edge: Edge<W>) {
         let Edge(src, dst) = edge;
-        self.nodes.insert(src);
-        self.edges.entry(src)
-            .or_default()
-            .insert(dst);
-        let NC(dst,cost) = dst else { panic!("") };
-        self.nodes.insert(dst);
-        self.edges.entry(dst)
-            .or_default()
-            .insert(NC(src,cost));
+        self.insert_edge(src, dst);
     }
     /// Returns Graph's edges in the form of a MinHeap, that is,
     /// the lowest cost edge at the top of the heap
-    pub fn get_edges_by_cost(&self) -> BinaryHeap<Edge> {
+    pub fn get_edges_by_cost(&self) -> BinaryHeap<Edge<W>> {
         self.edges.iter()
             .fold(BinaryHeap::new(), |mut heap, (&src, edges)| {
                     heap.extend(
@@ -46,29 +39,36 @@ impl Graph {
 
     }
     // ANCHOR_END: graphs_mst_graph
-    /// Private function that contracts a `Graph` struct given an MST test file
-    fn load_file_mst(&mut self, filename: &str) -> &mut Graph {
-        use std::fs::File;
-        use std::io::{BufReader, BufRead};
-        use std::str::FromStr;
+    /// Loads an undirected weighted graph given an MST text input, that is,
+    /// a header line followed by one `src dst cost` edge per line
+    pub fn import_text_mst<R: BufRead>(reader: R) -> Result<Graph<W>, GraphError> {
+        let mut g = Graph::new(GraphKind::Undirected);
 
-        let hnd = File::open(filename).unwrap_or_else(|e| panic!("load_test_file(): Cannot open file `{filename}` = {e}"));
-        let buf = BufReader::new(hnd);
+        for (num, line) in reader.lines().enumerate().skip(1) {
+            // report lines & columns as they appear in a text editor
+            let num = num + 1;
+            let line = line.map_err(|error| GraphError::Io { line: num, error })?;
 
-        buf.lines()
-            .skip(1)
-            .into_iter()
-            .for_each(| line| {
-                let str = line.unwrap_or_else(|e| panic!("load_file_mst(): {e}"));
+            let mut column = 1;
+            let mut iter = line.split(' ');
+            let mut next_token = || {
+                let token = iter.next()
+                    .ok_or(GraphError::parse(num, column, ParseErrorKind::MissingToken))?;
+                let token_column = column;
+                column += token.len() + 1;
+                Ok::<_, GraphError>((token, token_column))
+            };
 
-                let mut iter = str.split(' ');
-                let src = usize::from_str(iter.next().unwrap()).unwrap_or_else(|e| panic!("load_file_mst(): {e}"));
-                let dst = usize::from_str(iter.next().unwrap()).unwrap_or_else(|e| panic!("load_file_mst(): {e}"));
-                let cost = i32::from_str(iter.next().unwrap()).unwrap_or_else(|e| panic!("load_file_mst(): {e}"));
+            let (txt, col) = next_token()?;
+            let src = Node::from_str(txt).map_err(|_| GraphError::parse(num, col, ParseErrorKind::InvalidNode(txt.to_string())))?;
+            let (txt, col) = next_token()?;
+            let dst = Node::from_str(txt).map_err(|_| GraphError::parse(num, col, ParseErrorKind::InvalidNode(txt.to_string())))?;
+            let (txt, col) = next_token()?;
+            let cost = W::from_str(txt).map_err(|_| GraphError::parse(num, col, ParseErrorKind::InvalidCost(txt.to_string())))?;
 
-                self.push_edge( Edge(src,NC(dst,cost)));
-            });
-        self
+            g.push_edge( Edge(src,NC(dst,cost)));
+        }
+        Ok(g)
     }
 }
 
@@ -76,7 +76,7 @@ impl Graph {
 /// BinaryHeap Step structure containing `Edge(src,(dst,cost))` tuple
 /// The `cost` is only used as the prioritisation key for the `Heap`
 /// Implementing MinHeap through reverse comparison of Other against Self
-impl PartialOrd for Edge {
+impl<W: Weight> PartialOrd for Edge<W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match other.1 {
             N(_) => other.partial_cmp(self),
@@ -87,7 +87,7 @@ impl PartialOrd for Edge {
         }
     }
 }
-impl Ord for Edge {
+impl<W: Weight> Ord for Edge<W> {
     fn cmp(&self, other: &Self) -> Ordering {
         self.partial_cmp(other).unwrap()
     }
@@ -96,14 +96,39 @@ impl Ord for Edge {
 
 #[cfg(test)]
 mod test {
+    use std::{fs::File, io::BufReader};
+    use crate::graphs::{Cost, ParseErrorKind::*};
     use super::*;
 
     #[test]
     fn test_file_load_edge_by_cost() {
         let filename = "src/greedy/txt/input_random_1_10.txt";
-        let mut g = Graph::new();
-        println!("{:?}", g.load_file_mst(filename).get_edges_by_cost() );
+        let g: Graph = Graph::import_text_mst(BufReader::new(File::open(filename).expect("cannot open file")))
+            .expect("graph couldn't be loaded");
+        println!("{:?}", g.get_edges_by_cost() );
         println!("{:?}", g.sum_edges());
         assert!(true)
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_import_text_mst_errors() {
+        let test_data = [
+            ("2 1\n1 2 x\n", (2, 5, InvalidCost("x".to_string()))),
+            ("2 1\n1 2 3\n1 -2 4\n", (3, 3, InvalidNode("-2".to_string()))),
+            ("2 1\n1 2\n", (2, 5, MissingToken)),
+        ];
+        for (input, (line, column, kind)) in test_data {
+            match Graph::<Cost>::import_text_mst(input.as_bytes()) {
+                Err(GraphError::Parse { line: l, column: c, kind: k }) => assert_eq!((l, c, k), (line, column, kind)),
+                other => panic!("Expected parse error, got {:?}", other)
+            }
+        }
+    }
+    #[test]
+    fn test_sum_edges_no_overflow() {
+        let edges = [(1, 2, u64::MAX >> 1), (2, 3, u64::MAX >> 2)];
+        let g = Graph::from_edge_list(&edges, GraphKind::Undirected);
+        assert_eq!(g.sum_edges(), (u64::MAX >> 1) + (u64::MAX >> 2));
+        let g = Graph::from_edge_list(&edges, GraphKind::Directed);
+        assert_eq!(g.sum_edges(), (u64::MAX >> 1) + (u64::MAX >> 2));
+    }
+}
@@ -1,5 +1,4 @@
-use crate::graphs::{Edge, Graph, NodeType::{N, NC}};
-use std::collections::{BinaryHeap, HashSet};
+use crate::{graphs::{Edge, Graph, GraphError, GraphKind, Node, NodeType::NC, union_find::UnionFind, weight::Weight}, hash_heap::indexed::IndexedHeap};
 
 /// Trait defining the capability calculate the minimum spanning tree of a graph
 /// given an input algorithm function()
@@ -10,9 +9,9 @@ pub trait MinimumSpanningTree {
 }
 
 /// Implementation of the Minimum Spanning Tree by the Graph struct
-impl MinimumSpanningTree for Graph {
-    type Output = Option<Graph>;
-    type Algo = fn(&Graph)->Self::Output;
+impl<W: Weight> MinimumSpanningTree for Graph<W> {
+    type Output = Result<Option<Graph<W>>, GraphError>;
+    type Algo = fn(&Graph<W>)->Self::Output;
     /// Implements function with algorithm parameterization
     fn min_spanning_tree(&self, algo:Self::Algo) -> Self::Output {
         algo(self)
@@ -20,112 +19,95 @@ impl MinimumSpanningTree for Graph {
 }
 
 /// Implement Helper Graph functions for minimum spanning tree algorithm
-impl Graph {
+impl<W: Weight> Graph<W> {
     // ANCHOR: graphs_mst_graph_prim
     /// MST using Prim's algorithm implementation
-    pub fn mst_prim(&self) -> Option<Graph> {
+    /// Returns `None` when the graph is not connected
+    pub fn mst_prim(&self) -> Result<Option<Graph<W>>, GraphError> {
+
+        // spanning trees are only defined over undirected graphs
+        self.ensure_kind(GraphKind::Undirected)?;
 
         // Create an empty Graph/Tree to add one edge at a time
         // we'll be using g.node as the Tree's Component invariant,
         // that is, the Component that contains all vertices absorbed by the Tree
-        let mut tree = Graph::new();
+        let mut tree = Graph::new(GraphKind::Undirected);
 
-        // Min-Ordered heap with all edges found crossing the evolving tree
-        let mut heap = BinaryHeap::<Edge>::new();
+        // Min-Ordered heap keyed by every node found crossing the evolving tree, holding
+        // the cheapest edge `(cost, src)` connecting the node to the tree component
+        let mut heap = IndexedHeap::<Node, (W, Node)>::new();
 
         // seed with first vertex
-        let &start = self.nodes.iter().next().unwrap();
-        heap.push(Edge(start, NC(start, 0)));
-
-        // spawn a node at a time until we have spawned all graph nodes
-        // while tree component isn't equal input component
-        while tree.nodes != self.nodes {
-            // spawn a new edge node from the queue with the smallest edge weight
-            let src = match heap.pop() {
-                // if the queue is empty, but still have nodes to spawn
-                // then either (a) the graph is not connected or (b) is a directed graph
-                None => return None,
-                // spawn the destination node from edge
-                Some(Edge(_, NC(dst, _))) => dst,
-                Some(Edge(_, N(_))) => panic!("mst_prim(): Extracted edge using wrong NodeType::N"),
-            };
-
-            // Add all edges that are crossing the tree Component given the spawned node
-            // and have not yet been spawned, that is, they are NOT already part of tree component
-            heap.extend(self.edges.get(&src)
-                .unwrap_or_else(|| panic!("mst_prim(): Node ({src}) has not edges; Graph is not undirected or connected"))
-                .iter()
+        let Some(&start) = self.nodes.iter().next() else { return Ok(Some(tree)) };
+        heap.push(start, (W::ZERO, start));
+
+        // spawn the node with the cheapest edge crossing the tree component, one at a time
+        while let Some((dst, (cost, src))) = heap.pop() {
+            // the first vertex has no edge leading to it
+            match src == dst {
+                true => { tree.nodes.insert(dst); },
+                false => tree.push_edge(Edge(src, NC(dst, cost)))
+            }
+
+            // Update the edges crossing the tree Component given the spawned node,
+            // that is, src IN Component X, dst NOT IN Component X
+            for &edge in self.edges.get(&dst).into_iter().flatten() {
+                let NC(next, cost) = edge else { panic!("mst_prim(): Extracted edge using wrong NodeType::N") };
                 // remove any edge node already in the mst, part of Component X
-                .filter(|&&dst| !tree.nodes.contains(&dst.into()))
-                // push edges crossing Component X, that is,
-                // src IN Component X, dst NOT IN Component X
-                .map(|&dst| Edge(src, dst))
-            );
-
-            // find the min-weigh edge that is crossing the current tree component
-            // don't remove from heap as we need to spawn dst node for the next iteration
-            while let Some(&edge) = heap.peek() {
-                let Edge(src, dst) = edge;
-                // Is this edge a stale or a valid one, that is, crosses the tree component
-                if HashSet::from([src, dst.into()]).is_subset(&tree.nodes) {
-                    // Some times heap holds older edges that, after few iterations they get stale,
-                    // that is, both edges nodes have been moved into the tree component
-                    heap.pop();
-                } else {
-                    // either src or dst edge nodes are outside the tree component
-                    // hence add the edge into the tree
-                    tree.push_edge(edge);
-                    // exit the while loop since we've found the edge with the min weight
-                    break
+                if tree.nodes.contains(&next) { continue }
+                // keep only the cheapest edge per node, rather than pushing stale edges into the heap
+                if !heap.decrease_key(&next, (cost, dst)) && !heap.contains(&next) {
+                    heap.push(next, (cost, dst));
                 }
             }
         }
-        Some(tree)
+        // if the heap is empty, but still have nodes to spawn
+        // then the graph is not connected
+        if tree.nodes != self.nodes {
+            return Ok(None)
+        }
+        Ok(Some(tree))
     }
     // ANCHOR_END: graphs_mst_graph_prim
     // ANCHOR: graphs_mst_graph_kruska
     /// MST using Kruskal's algorithm implementation
-    pub fn mst_kruska(&self) -> Option<Graph> {
+    /// Returns `None` when the graph is not connected
+    pub fn mst_kruska(&self) -> Result<Option<Graph<W>>, GraphError> {
+
+        // spanning trees are only defined over undirected graphs
+        self.ensure_kind(GraphKind::Undirected)?;
 
         // Get the ordered heap by lowest cost Edge on top
         let mut heap = self.get_edges_by_cost();
-        // Keeps the graph's components, that is, a super node is a graph component's lead node
-        // The initial state is for each node to be a lead component node with a component of its own
-        let mut snodes = self.get_super_nodes();
+        // Keeps the graph's components as disjoint sets
+        // The initial state is for each node to be a component of its own
+        let mut components = self.nodes.iter().copied().collect::<UnionFind>();
         // the output graph that will hold *only* the edges
         // that form the minimum spanning tree
-        let mut graph = Graph::new();
+        let mut graph = Graph::new(GraphKind::Undirected);
 
         // As long as more than 2 components
-        while snodes.len() > 1 {
+        while components.count() > 1 {
             // get the edge with the lowest cost
             // otherwise if we've run out of edges while there are 2 or more components
             // then the graph IS NOT CONNECTED
-            let Some(edge) = heap.pop() else { return None };
+            let Some(edge) = heap.pop() else { return Ok(None) };
             let Edge(src, NC(dst, _)) = edge else { panic!("mst_kruska() - Cannot find NodeType::NC") };
-            // print!("({src:2}->{dst:2}):{cost:6} - ");
-
-            // if src is not a super node then get its super node
-            let src = snodes.find_supernode(&src);
-            // if dst is not a super node then get its super node
-            let dst = snodes.find_supernode(&dst);
 
             // if src component differs from dst component then merge the two and save the edge connecting them
-            if src != dst {
-                snodes.merge_nodes(src, dst);
+            if components.union(src, dst) {
                 graph.push_edge(edge);
-                // println!("Store");
-            } else {
-                // println!("Skip");
             }
         }
-        Some(graph)
+        Ok(Some(graph))
     }
     // ANCHOR_END: graphs_mst_graph_kruska
 }
 
 #[cfg(test)]
 mod test {
+    use std::{fs::File, io::BufReader};
+    use crate::graphs::weight::OrderedFloat;
     use super::*;
 
     #[test]
@@ -138,11 +120,11 @@ mod test {
             ,("src/greedy/txt/input_random_20_100.txt", -183953)
         ];
         for (filename, result) in test_data {
-            let mut g = Graph::new();
             println!("{filename}");
-            let mst = g.load_file_mst(filename).min_spanning_tree(Graph::mst_kruska);
-            assert!(mst.is_some());
-            let graph = mst.unwrap();
+            let g: Graph = Graph::import_text_mst(BufReader::new(File::open(filename).expect("cannot open file")))
+                .expect("graph couldn't be loaded");
+            let mst = g.min_spanning_tree(Graph::mst_kruska);
+            let graph = mst.expect("graph is undirected").expect("graph is connected");
             let cost = graph.sum_edges();
             println!("Min Spanning Tree: ({cost}) {:?}",graph);
             assert_eq!(result, cost);
@@ -158,14 +140,44 @@ mod test {
             ,("src/greedy/txt/input_random_20_100.txt", -183953)
         ];
         for (filename, result) in test_data {
-            let mut g = Graph::new();
             println!("{filename}");
-            let mst = g.load_file_mst(filename).min_spanning_tree(Graph::mst_prim);
-            assert!(mst.is_some());
-            let graph = mst.unwrap();
+            let g: Graph = Graph::import_text_mst(BufReader::new(File::open(filename).expect("cannot open file")))
+                .expect("graph couldn't be loaded");
+            let mst = g.min_spanning_tree(Graph::mst_prim);
+            let graph = mst.expect("graph is undirected").expect("graph is connected");
             let cost = graph.sum_edges();
             println!("Min Spanning Tree: ({cost}) {:?}",graph);
             assert_eq!(result, cost);
         }
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_mst_reject_directed() {
+        let g = Graph::from_edge_list(&[(1, 2, 1), (2, 3, 1)], GraphKind::Directed);
+        assert!(matches!(g.min_spanning_tree(Graph::mst_prim), Err(GraphError::KindMismatch { .. })));
+        assert!(matches!(g.min_spanning_tree(Graph::mst_kruska), Err(GraphError::KindMismatch { .. })));
+    }
+    #[test]
+    fn test_mst_disconnected() {
+        let mut g = Graph::from_edge_list(&[(1, 2, 1), (2, 3, 1)], GraphKind::Undirected);
+        g.nodes.insert(4);
+        assert_eq!(g.min_spanning_tree(Graph::mst_prim).expect("graph is undirected"), None);
+        assert_eq!(g.min_spanning_tree(Graph::mst_kruska).expect("graph is undirected"), None);
+    }
+    #[test]
+    fn test_mst_weight_types() {
+        let edges = [(1, 2, 1.5), (2, 3, 0.5), (1, 3, 1.0), (3, 4, 2.25)]
+            .map(|(src, dst, cost)| (src, dst, OrderedFloat(cost)));
+        let g = Graph::from_edge_list(&edges, GraphKind::Undirected);
+        for algo in [Graph::mst_prim, Graph::mst_kruska] {
+            let mst = g.min_spanning_tree(algo).expect("graph is undirected").expect("graph is connected");
+            assert_eq!(mst.sum_edges(), OrderedFloat(3.75));
+        }
+
+        let edges = [(1, 2, 4_000_000_000_i64), (2, 3, -5_000_000_000), (1, 3, 3_000_000_000)];
+        let g = Graph::from_edge_list(&edges, GraphKind::Undirected);
+        for algo in [Graph::mst_prim, Graph::mst_kruska] {
+            let mst = g.min_spanning_tree(algo).expect("graph is undirected").expect("graph is connected");
+            assert_eq!(mst.sum_edges(), -2_000_000_000);
+        }
+    }
+}
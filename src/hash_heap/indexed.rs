@@ -0,0 +1,192 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Indexed min-heap, that is, a binary heap of `(key, priority)` pairs where every key appears at most once
+/// A `HashMap` keeps each key's position in the heap, hence a queued key can have its priority lowered,
+/// or be removed altogether, in `O(log n)` rather than pushing duplicates and skipping the stale entries later
+/// ```
+/// use csx3::hash_heap::indexed::IndexedHeap;
+///
+/// let mut heap = IndexedHeap::new();
+/// heap.push('a', 5);
+/// heap.push('b', 3);
+/// heap.push('c', 4);
+/// assert!(heap.decrease_key(&'a', 1));
+/// assert_eq!(heap.pop(), Some(('a', 1)));
+/// assert_eq!(heap.remove(&'b'), Some(3));
+/// assert_eq!(heap.pop(), Some(('c', 4)));
+/// assert!(heap.is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndexedHeap<K, P> {
+    heap: Vec<(K, P)>,
+    index: HashMap<K, usize>
+}
+
+impl<K, P> IndexedHeap<K, P>
+    where K: Hash + Eq + Clone, P: Ord {
+
+    /// Construct an empty heap
+    pub fn new() -> IndexedHeap<K, P> {
+        IndexedHeap { heap: Vec::new(), index: HashMap::new() }
+    }
+    /// Number of keys in the heap
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+    /// Is the heap empty ?
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+    /// Is the key in the heap ?
+    pub fn contains(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+    /// Priority of the key, if it's in the heap
+    pub fn priority(&self, key: &K) -> Option<&P> {
+        self.index.get(key).map(|&pos| &self.heap[pos].1)
+    }
+    /// The key with the lowest priority, without removing it
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap.first().map(|(key, priority)| (key, priority))
+    }
+    /// Adds the key with the given priority; a key already in the heap has its priority replaced
+    /// Returns the key's previous priority, if any
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if let Some(&pos) = self.index.get(&key) {
+            let old = std::mem::replace(&mut self.heap[pos].1, priority);
+            let pos = self.sift_up(pos);
+            self.sift_down(pos);
+            return Some(old)
+        }
+        self.heap.push((key.clone(), priority));
+        self.index.insert(key, self.heap.len() - 1);
+        self.sift_up(self.heap.len() - 1);
+        None
+    }
+    /// Removes and returns the key with the lowest priority
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        self.take(0)
+    }
+    // ANCHOR: hash_heap_indexed_decrease_key
+    /// Lowers the priority of a key in the heap
+    /// Returns `false` and leaves the heap untouched if the key isn't in the heap or the priority isn't lower
+    pub fn decrease_key(&mut self, key: &K, priority: P) -> bool {
+        match self.index.get(key) {
+            Some(&pos) if priority < self.heap[pos].1 => {
+                self.heap[pos].1 = priority;
+                self.sift_up(pos);
+                true
+            },
+            _ => false
+        }
+    }
+    // ANCHOR_END: hash_heap_indexed_decrease_key
+    /// Removes the key from the heap and returns its priority
+    pub fn remove(&mut self, key: &K) -> Option<P> {
+        let pos = *self.index.get(key)?;
+        self.take(pos).map(|(_, priority)| priority)
+    }
+
+    /// Removes the entry at `pos` by swapping the last entry into its place and restoring the heap order
+    fn take(&mut self, pos: usize) -> Option<(K, P)> {
+        if pos >= self.heap.len() { return None }
+        let last = self.heap.len() - 1;
+        self.swap(pos, last);
+        let (key, priority) = self.heap.pop().expect("heap isn't empty");
+        self.index.remove(&key);
+        if pos < self.heap.len() {
+            let pos = self.sift_up(pos);
+            self.sift_down(pos);
+        }
+        Some((key, priority))
+    }
+    /// Moves the entry at `pos` up while it's lower than its parent; returns its final position
+    fn sift_up(&mut self, mut pos: usize) -> usize {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if self.heap[pos].1 >= self.heap[parent].1 { break }
+            self.swap(pos, parent);
+            pos = parent;
+        }
+        pos
+    }
+    /// Moves the entry at `pos` down while it's greater than its lowest child
+    fn sift_down(&mut self, mut pos: usize) {
+        loop {
+            let (left, right) = (2 * pos + 1, 2 * pos + 2);
+            let mut min = pos;
+            if left < self.heap.len() && self.heap[left].1 < self.heap[min].1 { min = left }
+            if right < self.heap.len() && self.heap[right].1 < self.heap[min].1 { min = right }
+            if min == pos { break }
+            self.swap(pos, min);
+            pos = min;
+        }
+    }
+    /// Swaps two entries, keeping the index in sync
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.index.insert(self.heap[a].0.clone(), a);
+        self.index.insert(self.heap[b].0.clone(), b);
+    }
+}
+
+impl<K, P> Default for IndexedHeap<K, P>
+    where K: Hash + Eq + Clone, P: Ord {
+    fn default() -> Self { IndexedHeap::new() }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+    use super::*;
+
+    #[test]
+    fn test_indexed_heap_order() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut heap = IndexedHeap::new();
+        let mut expected = (0..1000).map(|key| (key, rng.gen_range(0..100))).collect::<HashMap<_, _>>();
+        expected.iter().for_each(|(&key, &priority)| { heap.push(key, priority); });
+
+        // lower some priorities, raise others and drop a few keys altogether
+        for key in (0..1000).step_by(3) {
+            let priority = expected[&key] - 50;
+            assert!(heap.decrease_key(&key, priority));
+            assert!(!heap.decrease_key(&key, priority));
+            expected.insert(key, priority);
+        }
+        for key in (1..1000).step_by(7) {
+            assert_eq!(heap.push(key, expected[&key] + 10), Some(expected[&key]));
+            expected.insert(key, expected[&key] + 10);
+        }
+        for key in (2..1000).step_by(11) {
+            assert_eq!(heap.remove(&key), expected.remove(&key));
+            assert!(!heap.contains(&key));
+        }
+        assert_eq!(heap.remove(&2), None);
+        assert_eq!(heap.len(), expected.len());
+
+        let mut popped = Vec::new();
+        while let Some((key, priority)) = heap.pop() {
+            assert_eq!(expected.remove(&key), Some(priority));
+            popped.push(priority);
+        }
+        assert!(expected.is_empty());
+        assert!(popped.windows(2).all(|w| w[0] <= w[1]));
+    }
+    #[test]
+    fn test_indexed_heap_keys() {
+        let mut heap = IndexedHeap::new();
+        assert_eq!(heap.pop(), None::<(&str, i32)>);
+        assert_eq!(heap.push("a", 3), None);
+        assert_eq!(heap.push("b", 2), None);
+        assert!(heap.contains(&"a"));
+        assert_eq!(heap.priority(&"a"), Some(&3));
+        assert_eq!(heap.peek(), Some((&"b", &2)));
+        assert!(!heap.decrease_key(&"z", 1));
+        assert!(heap.decrease_key(&"a", 1));
+        assert_eq!(heap.peek(), Some((&"a", &1)));
+        assert_eq!(heap.remove(&"a"), Some(1));
+        assert_eq!(heap.pop(), Some(("b", 2)));
+        assert!(heap.is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+pub mod indexed;
+
 use std::collections::{HashMap, HashSet};
 
 /// Returns ONLY the first pair that sums up to the target value
@@ -16,7 +16,7 @@ pub mod merge;
 pub mod graphs;
 /// Greedy algorithms for scheduling, mimium spanning trees
 pub mod greedy;
-/// Greedy algorithms for scheduling, mimium spanning trees
+/// Hash table & heap algorithms, such as 2-sum and an indexed min-heap with decrease-key
 pub mod hash_heap;
 /// Dynamic Programming algorithms
 pub mod dp;
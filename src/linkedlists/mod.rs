@@ -141,6 +141,29 @@ impl<T> FromIterator<T> for List<T>
     }
 }
 
+/// A List serializes as a sequence of its items, from first to last, e.g. `[1, 2, 3]`
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for List<T>
+    where T: Copy + Clone + Ord + Debug + serde::Serialize {
+
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for List<T>
+    where T: Copy + Clone + Ord + Debug + serde::Deserialize<'de> {
+
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // pushing to the head in reverse order restores the original item order
+        let items = Vec::<T>::deserialize(deserializer)?;
+        let mut list = List::new();
+        items.into_iter().rev().for_each(|item| list.push_first(item));
+        Ok(list)
+    }
+}
+
 /// List by reference iterator
 pub struct ListIterByRef<'a, T>
     where T: Copy + Clone + Ord {
@@ -267,4 +290,16 @@ mod tests {
         assert_eq!(l.pop_last(), Some(3));
         assert_eq!(l.pop_last(), None);
     }
-}
\ No newline at end of file
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut l = List::new();
+        l.push_last(1);
+        l.push_last(2);
+        l.push_last(3);
+
+        let json = serde_json::to_string(&l).expect("list serializes");
+        assert_eq!(json, "[1,2,3]");
+        assert_eq!(serde_json::from_str::<List<i32>>(&json).expect("list deserializes"), l);
+    }
+}
@@ -5,7 +5,9 @@ pub enum BinaryTree<T> {
     NonEmpty(Box<TreeNode<T>>)
 }
 
+/// A tree node serializes as `{"val": val, "left": tree, "right": tree}`, with an empty tree being `null`
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TreeNode<T> {
     val: T,
     left: BinaryTree<T>,
@@ -84,6 +86,26 @@ impl<T> FromIterator<T> for BinaryTree<T>
     }
 }
 
+/// A BinaryTree serializes as `null` when empty or its root `TreeNode` otherwise, hence preserving the tree's shape, e.g.
+/// `{"val": 41, "left": {"val": 50, "left": null, "right": null}, "right": null}`
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for BinaryTree<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            BinaryTree::Empty => serializer.serialize_none(),
+            BinaryTree::NonEmpty(node) => serializer.serialize_some(node)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for BinaryTree<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Option::<Box<TreeNode<T>>>::deserialize(deserializer)?
+            .map_or(BinaryTree::Empty, BinaryTree::NonEmpty))
+    }
+}
+
 impl<'a, T> Iterator for TreeIter<'a, T> {
     type Item = &'a T;
 
@@ -113,4 +135,17 @@ mod test {
             vec![60,50,45,41,40]
         );
     }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut a = BinaryTree::new(41);
+        a.add(50);
+        a.add(40);
+
+        let json = serde_json::to_string(&a).expect("tree serializes");
+        assert_eq!(json, r#"{"val":41,"left":{"val":50,"left":null,"right":null},"right":{"val":40,"left":null,"right":null}}"#);
+        let b: BinaryTree<i32> = serde_json::from_str(&json).expect("tree deserializes");
+        assert_eq!(b.iter().collect::<Vec<_>>(), a.iter().collect::<Vec<_>>());
+        assert_eq!(serde_json::to_string(&BinaryTree::<i32>::Empty).expect("tree serializes"), "null");
+    }
 }